@@ -0,0 +1,160 @@
+use proc_macro2::{TokenStream, TokenTree};
+use syn::{Item, Path, Token, braced, parse2, parse_str};
+use syn::parse::{Parse, ParseStream, Parser, Result, Error};
+use syn::parse::discouraged::Speculative;
+use quote::quote;
+
+use crate::{ItemArgument, generate_macrospace_invokation};
+
+// The annotated item is parsed as whatever type the consumer function
+// declares for it, such as `ItemStruct` or `DeriveInput`.
+pub struct ConsumerData <A, I = Item>
+{
+	pub args: A,
+	pub annotated: I
+}
+
+impl <A, I> Parse for ConsumerData <A, I>
+where A: Parse, I: Parse
+{
+	fn parse (input: ParseStream <'_>) -> Result <Self>
+	{
+		let content;
+		braced! (content in input);
+		let args_tokens: TokenStream = content . parse ()?;
+
+		let args = parse2 (args_tokens)?;
+		let annotated = input . parse ()?;
+
+		Ok (Self {args, annotated})
+	}
+}
+
+fn parse_item_arguments (input: ParseStream <'_>) -> Result <Vec <ItemArgument>>
+{
+	let mut item_arguments = Vec::new ();
+
+	while ! input . is_empty () && ! input . peek (Token! [;])
+	{
+		item_arguments . push (input . parse ()?);
+
+		if input . peek (Token! [,])
+		{
+			input . parse::<Token! [,]> ()?;
+		}
+		else
+		{
+			break;
+		}
+	}
+
+	Ok (item_arguments)
+}
+
+// Consumer arguments are `items; args`, `items` or `args`.  Without a `;`,
+// anything that is not a list of item arguments is taken to be all args.
+fn parse_consumer_args (input: ParseStream <'_>)
+-> Result <(Vec <ItemArgument>, TokenStream)>
+{
+	let has_separator = input
+		. fork ()
+		. parse::<TokenStream> ()?
+		. into_iter ()
+		. any (|token| matches! (token, TokenTree::Punct (punct) if punct . as_char () == ';'));
+
+	let item_input = input . fork ();
+
+	match parse_item_arguments (&item_input)
+	{
+		Ok (item_arguments) if item_input . is_empty () || item_input . peek (Token! [;]) =>
+		{
+			input . advance_to (&item_input);
+
+			if ! input . is_empty ()
+			{
+				input . parse::<Token! [;]> ()?;
+			}
+
+			Ok ((item_arguments, input . parse ()?))
+		},
+		Ok (_) if has_separator =>
+			Err (item_input . error ("Expected `,` or `;` after an item argument")),
+		Err (error) if has_separator => Err (error),
+		_ => Ok ((Vec::new (), input . parse ()?))
+	}
+}
+
+fn try_invoke_consumer
+(
+	inner_macro_path: &str,
+	args: TokenStream,
+	annotated: TokenStream
+)
+-> Result <TokenStream>
+{
+	let inner_macro_path: Path = parse_str (inner_macro_path)?;
+	let (item_arguments, args) = parse_consumer_args . parse2 (args)?;
+
+	Ok
+	(
+		generate_macrospace_invokation
+		(
+			inner_macro_path,
+			item_arguments,
+			quote! ({#args} #annotated)
+		)
+	)
+}
+
+pub fn invoke_attribute_consumer
+(
+	inner_macro_path: &str,
+	attr: TokenStream,
+	item: TokenStream
+)
+-> TokenStream
+{
+	try_invoke_consumer (inner_macro_path, attr, item)
+		. unwrap_or_else (Error::into_compile_error)
+}
+
+fn try_invoke_derive_consumer
+(
+	inner_macro_path: &str,
+	helper_attribute: &str,
+	item: TokenStream
+)
+-> Result <TokenStream>
+{
+	let annotated: Item = parse2 (item . clone ())?;
+
+	let attrs = match &annotated
+	{
+		Item::Enum (item_enum) => &item_enum . attrs,
+		Item::Struct (item_struct) => &item_struct . attrs,
+		Item::Union (item_union) => &item_union . attrs,
+		_ => return Err (Error::new_spanned (&annotated, "Unsupported item"))
+	};
+
+	let args = match attrs
+		. iter ()
+		. find (|attr| attr . path () . is_ident (helper_attribute))
+	{
+		Some (attr) => attr . meta . require_list ()? . tokens . clone (),
+		None => TokenStream::new ()
+	};
+
+	try_invoke_consumer (inner_macro_path, args, item)
+}
+
+pub fn invoke_derive_consumer
+(
+	inner_macro_path: &str,
+	helper_attribute: &str,
+	item: TokenStream
+)
+-> TokenStream
+{
+	try_invoke_derive_consumer (inner_macro_path, helper_attribute, item)
+		. unwrap_or_else (Error::into_compile_error)
+}
@@ -0,0 +1,68 @@
+use syn::{Ident, Item, ForeignItemFn, Path, Token, parenthesized};
+use syn::token::Paren;
+use syn::parse::{Parse, ParseStream, Result};
+use syn::ext::IdentExt;
+use quote::ToTokens;
+
+use crate::{SelectedMember, fn_signature};
+
+// When a member was requested, `path` and `kind` describe the member, and
+// `item` is the item that it was selected from.
+#[derive (Clone)]
+pub struct FetchedItem
+{
+	pub path: Path,
+	pub kind: Ident,
+	pub defining_crate: Path,
+	pub item: Item,
+	pub member: Option <SelectedMember>
+}
+
+impl Parse for FetchedItem
+{
+	fn parse (input: ParseStream <'_>) -> Result <Self>
+	{
+		let content;
+		parenthesized! (content in input);
+
+		let path = content . parse ()?;
+		content . parse::<Token! [:]> ()?;
+		let kind = Ident::parse_any (&content)?;
+		content . parse::<Token! [,]> ()?;
+		let defining_crate = content . parse ()?;
+
+		let item = input . parse ()?;
+
+		Ok (Self {path, kind, defining_crate, item, member: None})
+	}
+}
+
+impl FetchedItem
+{
+	// The structure of a function exported with `#[item (signature)]`, whose
+	// `item` can only be represented verbatim.
+	pub fn fn_signature (&self) -> Option <ForeignItemFn>
+	{
+		fn_signature (&self . item)
+	}
+}
+
+impl ToTokens for FetchedItem
+{
+	fn to_tokens (&self, tokens: &mut proc_macro2::TokenStream)
+	{
+		Paren::default () . surround
+		(
+			tokens,
+			|inner_tokens|
+			{
+				self . path . to_tokens (inner_tokens);
+				<Token! [:]>::default () . to_tokens (inner_tokens);
+				self . kind . to_tokens (inner_tokens);
+				<Token! [,]>::default () . to_tokens (inner_tokens);
+				self . defining_crate . to_tokens (inner_tokens);
+			}
+		);
+		self . item . to_tokens (tokens);
+	}
+}
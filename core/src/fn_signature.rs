@@ -0,0 +1,110 @@
+use syn::{
+	Attribute,
+	Visibility,
+	Signature,
+	Block,
+	Item,
+	ItemFn,
+	ImplItem,
+	ImplItemFn,
+	ForeignItemFn,
+	Token,
+	parse2
+};
+use syn::parse::{Parse, ParseStream, Result};
+use quote::quote;
+
+// `#[item (signature)]` exports functions as `vis sig;`.  syn only knows
+// bodiless functions inside extern blocks and traits, so these travel as
+// verbatim items and are reparsed wherever their structure is needed.
+
+struct ImplFnSignature
+{
+	attrs: Vec <Attribute>,
+	vis: Visibility,
+	defaultness: Option <Token! [default]>,
+	sig: Signature
+}
+
+impl Parse for ImplFnSignature
+{
+	fn parse (input: ParseStream <'_>) -> Result <Self>
+	{
+		let attrs = input . call (Attribute::parse_outer)?;
+		let vis = input . parse ()?;
+		let defaultness = input . parse ()?;
+		let sig = input . parse ()?;
+		input . parse::<Token! [;]> ()?;
+
+		Ok (Self {attrs, vis, defaultness, sig})
+	}
+}
+
+fn empty_block () -> Box <Block>
+{
+	Box::new (Block {brace_token: Default::default (), stmts: Vec::new ()})
+}
+
+pub fn fn_signature (item: &Item) -> Option <ForeignItemFn>
+{
+	match item
+	{
+		Item::Verbatim (tokens) => parse2 (tokens . clone ()) . ok (),
+		_ => None
+	}
+}
+
+pub fn strip_fn_body (item_fn: ItemFn) -> Item
+{
+	let attrs = item_fn . attrs;
+	let vis = item_fn . vis;
+	let sig = item_fn . sig;
+
+	Item::Verbatim (quote! (#(#attrs)* #vis #sig;))
+}
+
+pub fn strip_impl_fn_body (impl_fn: ImplItemFn) -> ImplItem
+{
+	let attrs = impl_fn . attrs;
+	let vis = impl_fn . vis;
+	let defaultness = impl_fn . defaultness;
+	let sig = impl_fn . sig;
+
+	ImplItem::Verbatim (quote! (#(#attrs)* #vis #defaultness #sig;))
+}
+
+// The inverses give the signature an empty body, so that it can go through
+// the same folds as a complete function before being stripped again.
+pub fn fn_with_empty_body (signature: ForeignItemFn) -> ItemFn
+{
+	ItemFn
+	{
+		attrs: signature . attrs,
+		vis: signature . vis,
+		sig: signature . sig,
+		block: empty_block ()
+	}
+}
+
+pub fn impl_fn_with_empty_body (impl_item: &ImplItem) -> Option <ImplItemFn>
+{
+	let ImplItem::Verbatim (tokens) = impl_item
+	else
+	{
+		return None;
+	};
+
+	let signature: ImplFnSignature = parse2 (tokens . clone ()) . ok ()?;
+
+	Some
+	(
+		ImplItemFn
+		{
+			attrs: signature . attrs,
+			vis: signature . vis,
+			defaultness: signature . defaultness,
+			sig: signature . sig,
+			block: *empty_block ()
+		}
+	)
+}
@@ -1,13 +1,27 @@
-use syn::{Attribute, Visibility, Ident, parse_quote};
-use quote::{ToTokens, quote, format_ident};
+use syn::{Attribute, Visibility, Ident, Path, parse_quote};
+use quote::{ToTokens, quote};
+use itertools::Itertools;
 
-use crate::{get_macro_ident, sanitize};
+use crate::{
+	PROTOCOL_VERSION,
+	ProtocolHeader,
+	get_macro_ident,
+	get_mangled_macro_ident,
+	item_hash,
+	sanitize,
+	expand_defining_crate
+};
 
+#[allow (clippy::too_many_arguments)]
 pub fn generate_item_macro <T>
 (
 	item_ident: &Ident,
 	item_type: &Ident,
 	item_visibility: &Visibility,
+	macrospace_path: &Path,
+	signature_only: bool,
+	module_path: Option <&[Ident]>,
+	hash_scope: &[Ident],
 	item: &T
 )
 -> proc_macro2::TokenStream
@@ -15,7 +29,47 @@ where T: ToTokens
 {
 	let macro_ident = get_macro_ident (item_ident);
 
-	let mangled_macro_ident = format_ident! ("__{}__", macro_ident);
+	// Every child of a recursive export shares the call site of its attribute,
+	// so identical children are told apart by the modules they are in.
+	let module_idents = module_path . unwrap_or_default ();
+	let item_hash = item_hash (&quote! (#(#module_idents)* #(#hash_scope)* #item));
+	let mangled_macro_ident = get_mangled_macro_ident (item_ident, item_hash);
+
+	let protocol_header = ProtocolHeader::new ();
+
+	let crate_name = std::env::var ("CARGO_CRATE_NAME") . unwrap_or_default ();
+	let item_description = match module_path
+	{
+		Some (module_path) => format!
+		(
+			"`{}`",
+			[crate_name . clone ()]
+				. into_iter ()
+				. chain (module_path . iter () . map (ToString::to_string))
+				. chain ([item_ident . to_string ()])
+				. format ("::")
+		),
+		None => format! ("`{}` from crate `{}`", item_ident, crate_name)
+	};
+
+	let version_mismatch_message = format!
+	(
+		"{} (item hash {:016x}) was exported with macrospace protocol version {}, \
+		but was requested with protocol version ",
+		item_description,
+		item_hash,
+		PROTOCOL_VERSION
+	);
+
+	let missing_version_message = format!
+	(
+		"{} (item hash {:016x}) was exported with macrospace protocol version {}, \
+		but was requested without a protocol version; \
+		make sure that all crates use the same version of macrospace",
+		item_description,
+		item_hash,
+		PROTOCOL_VERSION
+	);
 
 	let export_attribute: Option <Attribute> = match item_visibility
 	{
@@ -23,8 +77,36 @@ where T: ToTokens
 		_ => None
 	};
 
-	let sanitized_item = sanitize (item);
+	let signature_marker = signature_only . then (|| quote! (signature));
 
+	let sanitized_item = expand_defining_crate (sanitize (item));
+
+	let next_macro_call = quote!
+	{
+		$next_macro_path!
+		(
+			#protocol_header
+			$next_item_path: [$($next_item_types)*]
+			($($($item_args)*)?)
+			$inner_macro_path
+			{$($items)* ($this_item_path: #item_type, $crate) #sanitized_item}
+			[$($tokens)*]
+		);
+	};
+
+	let inner_macro_call = quote!
+	{
+		$inner_macro_path!
+		(
+			{$($items)* ($this_item_path: #item_type, $crate) #sanitized_item}
+			[$($tokens)*]
+		);
+	};
+
+	// A request for exactly the item's own kind always passes the check, so it
+	// is matched literally and goes straight on to the next macro.  Any other
+	// request has `check_item_type!` expand the next macro only if it passes,
+	// so that a mismatch is not followed by errors from the wrong item.
 	quote!
 	{
 		#[doc (hidden)]
@@ -32,72 +114,87 @@ where T: ToTokens
 		macro_rules! #mangled_macro_ident
 		{
 			(
-				$this_item_path: path: $($this_item_types: tt)|*
+				#protocol_header
+				$this_item_path: path: [#item_type]
 				(
-					$next_item_path: path: $($next_item_types: tt)|*,
-					$($item_args: tt)*
+					$next_item_path: path: [$($next_item_types: tt)*] => $next_macro_path: path
+					$(, $($item_args: tt)*)?
 				)
 				$inner_macro_path: path
 				{$($items: tt)*}
 				[$($tokens: tt)*]
 			) =>
 			{
-				macrospace::check_item_type!
-				(
-					$this_item_path: #item_type == $($this_item_types)|*
-					{
-						macrospace::invoke_item_macro!
-						(
-							$next_item_path: $($next_item_types)|*
-							($($item_args)*)
-							$inner_macro_path
-							{$($items)* #sanitized_item}
-							[$($tokens)*]
-						);
-					}
-				);
+				#next_macro_call
 			};
 			(
-				$this_item_path: path: $($this_item_types: tt)|*
+				#protocol_header
+				$this_item_path: path: [$($this_item_types: tt)*]
 				(
-					$next_item_path: path: $($next_item_types: tt)|*
+					$next_item_path: path: [$($next_item_types: tt)*] => $next_macro_path: path
+					$(, $($item_args: tt)*)?
 				)
 				$inner_macro_path: path
 				{$($items: tt)*}
 				[$($tokens: tt)*]
 			) =>
 			{
-				macrospace::check_item_type!
+				#macrospace_path::check_item_type!
 				(
-					$this_item_path: #item_type == $($this_item_types)|*
-					{
-						macrospace::invoke_item_macro!
-						(
-							$next_item_path: $($next_item_types)|*
-							()
-							$inner_macro_path
-							{$($items)* #sanitized_item}
-							[$($tokens)*]
-						);
-					}
+					#protocol_header
+					$this_item_path: #item_type #signature_marker == [$($this_item_types)*]
+					{#sanitized_item}
+					{#next_macro_call}
 				);
 			};
 			(
-				$this_item_path: path: $($this_item_types: tt)|*
+				#protocol_header
+				$this_item_path: path: [#item_type]
 				()
 				$inner_macro_path: path
 				{$($items: tt)*}
 				[$($tokens: tt)*]
 			) =>
 			{
-				macrospace::check_item_type!
+				#inner_macro_call
+			};
+			(
+				#protocol_header
+				$this_item_path: path: [$($this_item_types: tt)*]
+				()
+				$inner_macro_path: path
+				{$($items: tt)*}
+				[$($tokens: tt)*]
+			) =>
+			{
+				#macrospace_path::check_item_type!
 				(
-					$this_item_path: #item_type == $($this_item_types)|*
-					{$inner_macro_path! ({$($items)* #sanitized_item} [$($tokens)*]);}
+					#protocol_header
+					$this_item_path: #item_type #signature_marker == [$($this_item_types)*]
+					{#sanitized_item}
+					{#inner_macro_call}
 				);
+			};
+			(@macrospace_protocol $version: tt $($tokens: tt)*) =>
+			{
+				compile_error!
+				(
+					concat!
+					(
+						#version_mismatch_message,
+						stringify! ($version),
+						"; make sure that all crates use the same version of macrospace"
+					)
+				);
+			};
+			($($tokens: tt)*) =>
+			{
+				compile_error! (#missing_version_message);
 			}
 		}
 
+		// Crate-local item macros are only there to be fetched, and may not be.
+		#[allow (unused_imports)]
 		#item_visibility use #mangled_macro_ident as #macro_ident;
 	}
 }
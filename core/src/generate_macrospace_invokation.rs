@@ -1,7 +1,7 @@
 use syn::Path;
 use quote::{ToTokens, quote};
 
-use crate::{ItemArgument, get_macro_path, sanitize};
+use crate::{ItemArgument, ProtocolHeader, get_macro_path, sanitize};
 
 pub fn generate_macrospace_invokation <I, T>
 (
@@ -14,33 +14,42 @@ where
 	I: IntoIterator <Item = ItemArgument>,
 	T: ToTokens
 {
+	let required_items: Vec <ItemArgument> =
+		required_items . into_iter () . collect ();
+
 	let mut item_paths = Vec::new ();
 	let mut item_type_specs = Vec::new ();
 
-	for item_argument in required_items
+	for item_argument in &required_items
 	{
-		item_paths . push (item_argument . path);
-		item_type_specs . push (item_argument . type_spec);
+		item_paths . push (item_argument . item_path ());
+		item_type_specs . push (item_argument . item_type_spec ());
 	}
 
+	// Macro paths are resolved up front so that each item macro can call the
+	// next one directly, keeping the expansion depth at one level per item.
+	let item_macro_paths: Vec <_> = item_paths . iter () . map (get_macro_path) . collect ();
+
 	let mut item_paths = item_paths . into_iter ();
 	let mut item_type_specs = item_type_specs . into_iter ();
+	let mut item_macro_paths = item_macro_paths . into_iter ();
 
 	let sanitized_additional_tokens = sanitize (additional_tokens);
 
-	if let (Some (first_item), Some (first_type_spec))
-		= (item_paths . next (), item_type_specs . next ())
+	if let (Some (first_item), Some (first_type_spec), Some (first_macro))
+		= (item_paths . next (), item_type_specs . next (), item_macro_paths . next ())
 	{
-		let first_macro = get_macro_path (&first_item);
+		let protocol_header = ProtocolHeader::new ();
 
 		quote!
 		{
 			#first_macro!
 			(
+				#protocol_header
 				#first_item: #first_type_spec
-				(#(#item_paths: #item_type_specs),*)
+				(#(#item_paths: #item_type_specs => #item_macro_paths),*)
 				#inner_macro_path
-				{}
+				{[#(#required_items),*]}
 				[#sanitized_additional_tokens]
 			);
 		}
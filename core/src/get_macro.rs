@@ -1,14 +1,21 @@
 use syn::{Ident, Path};
 use quote::format_ident;
 
+use crate::path_utils::without_arguments;
+
 pub fn get_macro_ident (ident: &Ident) -> Ident
 {
 	format_ident! ("macrospace_apply_{}", ident)
 }
 
+pub fn get_mangled_macro_ident (ident: &Ident, item_hash: u64) -> Ident
+{
+	format_ident! ("__macrospace_apply_{}_{:016x}__", ident, item_hash)
+}
+
 pub fn get_macro_path (path: &Path) -> Path
 {
-	let mut macro_path = path . clone ();
+	let mut macro_path = without_arguments (path . clone ());
 
 	if let Some (segment) = macro_path . segments . last_mut ()
 	{
@@ -0,0 +1,100 @@
+use std::collections::HashSet;
+
+use syn::{Item, ItemImpl, ImplItem, Path};
+use syn::parse::{Result, Error};
+
+use crate::{
+	ItemArgument,
+	fn_signature,
+	strip_fn_body,
+	strip_impl_fn_body,
+	fn_with_empty_body,
+	impl_fn_with_empty_body
+};
+use crate::substitute::{
+	substitute_arguments_for_struct,
+	substitute_arguments_for_enum,
+	substitute_arguments_for_trait,
+	substitute_arguments_for_fn,
+	substitute_arguments_for_impl
+};
+
+// Stripped impl fns are given their bodies back for substitution, then
+// stripped again, so their signatures are instantiated like everything else.
+fn instantiate_impl (mut item_impl: ItemImpl, item_path: &Path) -> Result <ItemImpl>
+{
+	let mut stripped = HashSet::new ();
+
+	for (i, impl_item) in item_impl . items . iter_mut () . enumerate ()
+	{
+		if let Some (impl_fn) = impl_fn_with_empty_body (impl_item)
+		{
+			*impl_item = ImplItem::Fn (impl_fn);
+			stripped . insert (i);
+		}
+	}
+
+	let (_, mut item_impl) = substitute_arguments_for_impl (item_impl, item_path)?;
+
+	item_impl . items = item_impl
+		. items
+		. into_iter ()
+		. enumerate ()
+		. map
+		(
+			|(i, impl_item)| match impl_item
+			{
+				ImplItem::Fn (impl_fn) if stripped . contains (&i) =>
+					strip_impl_fn_body (impl_fn),
+				impl_item => impl_item
+			}
+		)
+		. collect ();
+
+	Ok (item_impl)
+}
+
+fn try_instantiate_item (item: Item, item_path: &Path) -> Result <Item>
+{
+	if let Some (signature) = fn_signature (&item)
+	{
+		return substitute_arguments_for_fn (fn_with_empty_body (signature), item_path)
+			. map (|(_, item_fn)| strip_fn_body (item_fn));
+	}
+
+	match item
+	{
+		Item::Struct (item_struct) =>
+			substitute_arguments_for_struct (item_struct, item_path)
+				. map (|(_, item_struct)| Item::Struct (item_struct)),
+		Item::Enum (item_enum) =>
+			substitute_arguments_for_enum (item_enum, item_path)
+				. map (|(_, item_enum)| Item::Enum (item_enum)),
+		Item::Trait (item_trait) =>
+			substitute_arguments_for_trait (item_trait, item_path)
+				. map (|(_, item_trait)| Item::Trait (item_trait)),
+		Item::Fn (item_fn) =>
+			substitute_arguments_for_fn (item_fn, item_path)
+				. map (|(_, item_fn)| Item::Fn (item_fn)),
+		Item::Impl (item_impl) =>
+			instantiate_impl (item_impl, item_path) . map (Item::Impl),
+		_ => Err
+		(
+			Error::new_spanned
+			(
+				item_path,
+				"Only structs, enums, traits, functions and impl blocks can be instantiated"
+			)
+		)
+	}
+}
+
+pub fn instantiate_item (item_argument: &ItemArgument, item: Item) -> Result <Item>
+{
+	let item_path = item_argument . item_path ();
+
+	// Argument count errors are spanned at the arguments themselves, which are
+	// empty when none were given, so report everything at the requested path.
+	try_instantiate_item (item, &item_path)
+		. map_err (|error| Error::new_spanned (&item_path, error))
+}
@@ -0,0 +1,77 @@
+use proc_macro2::TokenStream;
+use syn::{Path, Token};
+use quote::ToTokens;
+
+use crate::{ItemArgument, ItemTypeSpec, generate_macrospace_invokation};
+
+#[derive (Clone)]
+pub struct Invocation
+{
+	pub inner_macro_path: Path,
+	pub required_items: Vec <ItemArgument>,
+	pub user_data: TokenStream
+}
+
+impl Invocation
+{
+	pub fn new (inner_macro_path: Path) -> Self
+	{
+		Self
+		{
+			inner_macro_path,
+			required_items: Vec::new (),
+			user_data: TokenStream::new ()
+		}
+	}
+
+	pub fn require (self, path: Path, type_spec: ItemTypeSpec) -> Self
+	{
+		self . require_argument
+		(
+			ItemArgument
+			{
+				path,
+				field_selector: None,
+				colon_token: <Token! [:]>::default (),
+				type_spec,
+				instantiate_token: None
+			}
+		)
+	}
+
+	pub fn require_argument (mut self, item_argument: ItemArgument) -> Self
+	{
+		self . required_items . push (item_argument);
+		self
+	}
+
+	pub fn user_data <T> (mut self, user_data: T) -> Self
+	where T: ToTokens
+	{
+		self . user_data = user_data . into_token_stream ();
+		self
+	}
+}
+
+impl Extend <ItemArgument> for Invocation
+{
+	fn extend <I> (&mut self, iter: I)
+	where I: IntoIterator <Item = ItemArgument>
+	{
+		self . required_items . extend (iter);
+	}
+}
+
+impl ToTokens for Invocation
+{
+	fn to_tokens (&self, tokens: &mut TokenStream)
+	{
+		generate_macrospace_invokation
+		(
+			self . inner_macro_path . clone (),
+			self . required_items . iter () . cloned (),
+			&self . user_data
+		)
+			. to_tokens (tokens);
+	}
+}
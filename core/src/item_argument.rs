@@ -1,12 +1,127 @@
-use syn::{Path, Token};
+use syn::{Path, Member, Token};
+use syn::parse::{Parse, ParseStream, Result, Error};
 use syn_derive::{Parse, ToTokens};
 
 use crate::ItemTypeSpec;
 
-#[derive (Parse, ToTokens)]
+#[derive (Clone, Parse, ToTokens)]
+pub struct FieldSelector
+{
+	pub dot_token: Token! [.],
+	pub member: Member
+}
+
+#[derive (Clone, ToTokens)]
 pub struct ItemArgument
 {
 	pub path: Path,
+	pub field_selector: Option <FieldSelector>,
 	pub colon_token: Token! [:],
-	pub type_spec: ItemTypeSpec
+	pub type_spec: ItemTypeSpec,
+	pub instantiate_token: Option <Token! [!]>
+}
+
+impl Parse for ItemArgument
+{
+	fn parse (input: ParseStream <'_>) -> Result <Self>
+	{
+		let path: Path = input . parse ()?;
+
+		let field_selector: Option <FieldSelector> =
+			if input . peek (Token! [.]) { Some (input . parse ()?) }
+			else { None };
+
+		let colon_token = input . parse ()?;
+		let type_spec: ItemTypeSpec = input . parse ()?;
+
+		type_spec . validate_member_selection ()?;
+
+		let instantiate_token: Option <Token! [!]> =
+			if input . peek (Token! [!]) { Some (input . parse ()?) }
+			else { None };
+
+		let selects_field = type_spec . names ("field");
+
+		match (&field_selector, selects_field)
+		{
+			(Some (field_selector), false) => return Err
+			(
+				Error::new_spanned
+				(
+					field_selector . dot_token,
+					"Field selectors require the `field` item type"
+				)
+			),
+			(None, true) => return Err
+			(
+				Error::new_spanned
+				(
+					&path,
+					"Selecting a field requires the `Path.field` syntax"
+				)
+			),
+			(Some (_), true) if type_spec . terms . len () > 1 => return Err
+			(
+				Error::new_spanned
+				(
+					&type_spec,
+					"Field selectors cannot be combined with other item types"
+				)
+			),
+			_ => {}
+		}
+
+		if field_selector . is_none ()
+			&& type_spec . selects_member ()
+			&& path . segments . len () < 2
+		{
+			return Err
+			(
+				Error::new_spanned
+				(
+					&path,
+					"Selecting a member requires a path to its parent item"
+				)
+			);
+		}
+
+		Ok (Self {path, field_selector, colon_token, type_spec, instantiate_token})
+	}
+}
+
+impl ItemArgument
+{
+	pub fn item_path (&self) -> Path
+	{
+		if self . field_selector . is_some () || ! self . type_spec . selects_member ()
+		{
+			return self . path . clone ();
+		}
+
+		let num_parent_segments = self . path . segments . len () - 1;
+
+		Path
+		{
+			leading_colon: self . path . leading_colon,
+			segments: self
+				. path
+				. segments
+				. iter ()
+				. take (num_parent_segments)
+				. cloned ()
+				. collect ()
+		}
+	}
+
+	pub fn item_type_spec (&self) -> ItemTypeSpec
+	{
+		if self . type_spec . selects_member ()
+		{
+			self . type_spec . parent_spec ()
+		}
+		else
+		{
+			self . type_spec . clone ()
+		}
+	}
 }
@@ -0,0 +1,420 @@
+use std::fmt::{Display, Formatter};
+
+use proc_macro2::{TokenStream, TokenTree};
+use syn::{
+	Ident,
+	Item,
+	Fields,
+	FnArg,
+	Generics,
+	GenericParam,
+	ItemTrait,
+	TraitItem,
+	TypeParamBound,
+	WherePredicate,
+	LitInt,
+	Token,
+	braced
+};
+use syn::token::Brace;
+use syn::punctuated::Punctuated;
+use syn::parse::{Parse, ParseStream, Result, Error};
+use syn_derive::{Parse, ToTokens};
+use quote::ToTokens;
+
+use crate::fn_signature;
+
+pub const GENERIC_ITEM_TYPES: &[&str] =
+&[
+	"alias",
+	"enum",
+	"fn",
+	"impl",
+	"struct",
+	"trait",
+	"type",
+	"union"
+];
+
+pub const ITEM_SHAPES: &[(&str, &[&str])] =
+&[
+	("full", &["fn", "impl", "mod", "trait"]),
+	("named", &["struct"]),
+	("newtype", &["enum"]),
+	("object_safe", &["trait"]),
+	("tuple", &["struct"]),
+	("unit", &["struct", "enum"])
+];
+
+#[derive (Clone, Debug, Parse, ToTokens)]
+pub struct GenericArity
+{
+	pub lt_token: Token! [<],
+	pub arity: LitInt,
+	pub gt_token: Token! [>]
+}
+
+#[derive (Clone, Debug, ToTokens)]
+pub struct ItemShape
+{
+	#[syn (braced)]
+	pub brace_token: Brace,
+	#[syn (in = brace_token)]
+	pub shapes: Punctuated <Ident, Token! [,]>
+}
+
+impl Parse for ItemShape
+{
+	fn parse (input: ParseStream <'_>) -> Result <Self>
+	{
+		let content;
+		let brace_token = braced! (content in input);
+		let shapes = Punctuated::parse_terminated (&content)?;
+
+		for shape in &shapes
+		{
+			if ! ITEM_SHAPES . iter () . any (|(known_shape, _)| shape == known_shape)
+			{
+				return Err
+				(
+					Error::new_spanned
+					(
+						shape,
+						format! ("Unknown item shape `{}`", shape)
+					)
+				);
+			}
+		}
+
+		Ok (Self {brace_token, shapes})
+	}
+}
+
+#[derive (Clone, Debug, Default, ToTokens)]
+pub struct ItemConstraints
+{
+	pub generic_arity: Option <GenericArity>,
+	pub shape: Option <ItemShape>
+}
+
+impl Parse for ItemConstraints
+{
+	fn parse (input: ParseStream <'_>) -> Result <Self>
+	{
+		let generic_arity =
+			if input . peek (Token! [<]) { Some (input . parse ()?) }
+			else { None };
+
+		let shape =
+			if input . peek (Brace) { Some (input . parse ()?) }
+			else { None };
+
+		Ok (Self {generic_arity, shape})
+	}
+}
+
+impl Display for ItemConstraints
+{
+	fn fmt (&self, f: &mut Formatter <'_>) -> std::fmt::Result
+	{
+		if let Some (generic_arity) = &self . generic_arity
+		{
+			write! (f, "<{}>", generic_arity . arity)?;
+		}
+
+		if let Some (shape) = &self . shape
+		{
+			write! (f, "{{")?;
+
+			for (i, shape) in shape . shapes . iter () . enumerate ()
+			{
+				if i > 0
+				{
+					write! (f, ", ")?;
+				}
+
+				write! (f, "{}", shape)?;
+			}
+
+			write! (f, "}}")?;
+		}
+
+		Ok (())
+	}
+}
+
+fn item_generics (item: &Item) -> Option <Generics>
+{
+	match item
+	{
+		Item::Enum (item) => Some (item . generics . clone ()),
+		Item::Fn (item) => Some (item . sig . generics . clone ()),
+		Item::Impl (item) => Some (item . generics . clone ()),
+		Item::Struct (item) => Some (item . generics . clone ()),
+		Item::Trait (item) => Some (item . generics . clone ()),
+		Item::TraitAlias (item) => Some (item . generics . clone ()),
+		Item::Type (item) => Some (item . generics . clone ()),
+		Item::Union (item) => Some (item . generics . clone ()),
+		Item::Verbatim (_) => fn_signature (item) . map (|signature| signature . sig . generics),
+		_ => None
+	}
+}
+
+fn mentions_self <T> (tokens: &T) -> bool
+where T: ToTokens
+{
+	fn stream_mentions_self (tokens: TokenStream) -> bool
+	{
+		tokens . into_iter () . any
+		(
+			|token| match token
+			{
+				TokenTree::Ident (ident) => ident == "Self",
+				TokenTree::Group (group) => stream_mentions_self (group . stream ()),
+				_ => false
+			}
+		)
+	}
+
+	stream_mentions_self (tokens . to_token_stream ())
+}
+
+fn is_sized_bound (bound: &TypeParamBound) -> bool
+{
+	matches!
+	(
+		bound,
+		TypeParamBound::Trait (trait_bound)
+			if trait_bound . path . is_ident ("Sized")
+	)
+}
+
+fn requires_self_sized (generics: &Generics) -> bool
+{
+	generics . where_clause . iter () . flat_map (|w| &w . predicates) . any
+	(
+		|predicate| matches!
+		(
+			predicate,
+			WherePredicate::Type (predicate_type)
+				if predicate_type . bounded_ty . to_token_stream () . to_string () == "Self"
+					&& predicate_type . bounds . iter () . any (is_sized_bound)
+		)
+	)
+}
+
+// This is an approximation from syntax alone.  Supertraits are not followed,
+// and associated types are only checked for generic parameters.
+fn object_safety_violation (item_trait: &ItemTrait) -> Option <String>
+{
+	if item_trait . supertraits . iter () . any (is_sized_bound)
+		|| requires_self_sized (&item_trait . generics)
+	{
+		return Some (format! ("`{}` requires `Self: Sized`", item_trait . ident));
+	}
+
+	for trait_item in &item_trait . items
+	{
+		match trait_item
+		{
+			TraitItem::Const (trait_const) => return Some
+			(
+				format! ("`{}` has associated const `{}`", item_trait . ident, trait_const . ident)
+			),
+			TraitItem::Type (trait_type) if ! trait_type . generics . params . is_empty () =>
+				return Some
+				(
+					format!
+					(
+						"`{}` has generic associated type `{}`",
+						item_trait . ident,
+						trait_type . ident
+					)
+				),
+			TraitItem::Fn (trait_fn) if ! requires_self_sized (&trait_fn . sig . generics) =>
+			{
+				let sig = &trait_fn . sig;
+
+				let violation =
+					if sig . receiver () . is_none () { Some ("has no receiver") }
+					else if sig . asyncness . is_some () { Some ("is async") }
+					else if sig
+						. generics
+						. params
+						. iter ()
+						. any (|param| ! matches! (param, GenericParam::Lifetime (_)))
+					{
+						Some ("has generic parameters")
+					}
+					else if sig . inputs . iter () . any
+					(
+						|input| matches! (input, FnArg::Typed (typed) if mentions_self (&typed . ty))
+					)
+						|| mentions_self (&sig . output)
+					{
+						Some ("mentions `Self` outside of its receiver")
+					}
+					else { None };
+
+				if let Some (violation) = violation
+				{
+					return Some
+					(
+						format!
+						(
+							"method `{}` of `{}` {}",
+							sig . ident,
+							item_trait . ident,
+							violation
+						)
+					);
+				}
+			},
+			_ => {}
+		}
+	}
+
+	None
+}
+
+fn shape_violation (shape: &Ident, item: &Item) -> Option <String>
+{
+	match (shape . to_string () . as_str (), item)
+	{
+		("named", Item::Struct (item_struct)) => match item_struct . fields
+		{
+			Fields::Named (_) => None,
+			_ => Some (format! ("`{}` does not have named fields", item_struct . ident))
+		},
+		("tuple", Item::Struct (item_struct)) => match item_struct . fields
+		{
+			Fields::Unnamed (_) => None,
+			_ => Some (format! ("`{}` is not a tuple struct", item_struct . ident))
+		},
+		("unit", Item::Struct (item_struct)) => match item_struct . fields
+		{
+			Fields::Unit => None,
+			_ => Some (format! ("`{}` is not a unit struct", item_struct . ident))
+		},
+		("unit", Item::Enum (item_enum)) => item_enum
+			. variants
+			. iter ()
+			. find (|variant| ! matches! (variant . fields, Fields::Unit))
+			. map (|variant| format! ("variant `{}` is not a unit variant", variant . ident)),
+		("newtype", Item::Enum (item_enum)) => item_enum
+			. variants
+			. iter ()
+			. find
+			(
+				|variant| ! matches!
+				(
+					&variant . fields,
+					Fields::Unnamed (unnamed) if unnamed . unnamed . len () == 1
+				)
+			)
+			. map
+			(
+				|variant| format!
+				(
+					"variant `{}` is not a tuple variant with exactly one field",
+					variant . ident
+				)
+			),
+		("object_safe", Item::Trait (item_trait)) =>
+			object_safety_violation (item_trait),
+		("full", _) => None,
+		(shape, _) => Some (format! ("item cannot have shape `{}`", shape))
+	}
+}
+
+impl ItemConstraints
+{
+	pub fn is_empty (&self) -> bool
+	{
+		self . generic_arity . is_none () && self . shape . is_none ()
+	}
+
+	pub fn requires_full (&self) -> bool
+	{
+		self
+			. shape
+			. iter ()
+			. flat_map (|shape| &shape . shapes)
+			. any (|shape| shape == "full")
+	}
+
+	pub fn validate (&self, applicable_types: &[&str]) -> Result <()>
+	{
+		if let Some (generic_arity) = &self . generic_arity
+		{
+			generic_arity . arity . base10_parse::<usize> ()?;
+
+			if ! applicable_types . iter () . any (|ty| GENERIC_ITEM_TYPES . contains (ty))
+			{
+				return Err
+				(
+					Error::new_spanned
+					(
+						generic_arity,
+						"Generic arity does not apply to this item type"
+					)
+				);
+			}
+		}
+
+		for shape in self . shape . iter () . flat_map (|shape| &shape . shapes)
+		{
+			let shape_types = ITEM_SHAPES
+				. iter ()
+				. find (|(known_shape, _)| shape == known_shape)
+				. map (|(_, shape_types)| *shape_types)
+				. unwrap_or_default ();
+
+			if ! applicable_types . iter () . any (|ty| shape_types . contains (ty))
+			{
+				return Err
+				(
+					Error::new_spanned
+					(
+						shape,
+						format! ("Shape `{}` does not apply to this item type", shape)
+					)
+				);
+			}
+		}
+
+		Ok (())
+	}
+
+	pub fn violation (&self, item: &Item) -> Option <String>
+	{
+		if let Some (generic_arity) = &self . generic_arity
+		{
+			let expected_arity: usize =
+				generic_arity . arity . base10_parse () . unwrap_or_default ();
+
+			let found_arity = item_generics (item)
+				. map (|generics| generics . params . len ())
+				. unwrap_or_default ();
+
+			if found_arity != expected_arity
+			{
+				return Some
+				(
+					format!
+					(
+						"expected {} generic parameters, found {}",
+						expected_arity,
+						found_arity
+					)
+				);
+			}
+		}
+
+		self
+			. shape
+			. iter ()
+			. flat_map (|shape| &shape . shapes)
+			. find_map (|shape| shape_violation (shape, item))
+	}
+}
@@ -0,0 +1,26 @@
+extern crate proc_macro;
+
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use quote::ToTokens;
+
+pub fn item_hash <T> (item: &T) -> u64
+where T: ToTokens
+{
+	let mut hasher = DefaultHasher::new ();
+
+	item . to_token_stream () . to_string () . hash (&mut hasher);
+
+	// Identical items in different modules can only be told apart by where
+	// they were written, and that is only known while a proc macro is running.
+	if proc_macro::is_available ()
+	{
+		let call_site = proc_macro::Span::call_site ();
+
+		call_site . file () . hash (&mut hasher);
+		call_site . line () . hash (&mut hasher);
+		call_site . column () . hash (&mut hasher);
+	}
+
+	hasher . finish ()
+}
@@ -0,0 +1,75 @@
+use syn::{Ident, Item, ItemMacro, Signature, Fields};
+use syn::parse::{Result, Error};
+use syn::spanned::Spanned;
+
+use crate::fn_signature;
+
+pub fn is_macro_rules (item: &ItemMacro) -> bool
+{
+	item . mac . path . is_ident ("macro_rules") && item . ident . is_some ()
+}
+
+fn fn_type (sig: &Signature) -> Ident
+{
+	let fn_type =
+		if sig . constness . is_some () { "const_fn" }
+		else if sig . asyncness . is_some () { "async_fn" }
+		else { "fn" };
+
+	Ident::new (fn_type, sig . fn_token . span ())
+}
+
+pub fn item_type (item: &Item) -> Result <Ident>
+{
+	match item
+	{
+		Item::Const (item) =>
+			Ok (Ident::new ("const", item . const_token . span ())),
+		Item::Enum (item) =>
+			Ok (Ident::new ("enum", item . enum_token . span ())),
+		Item::ExternCrate (item) =>
+			Ok (Ident::new ("extern", item . extern_token . span ())),
+		Item::Fn (item) => Ok (fn_type (&item . sig)),
+		Item::ForeignMod (item) =>
+			Ok (Ident::new ("extern_block", item . abi . extern_token . span ())),
+		Item::Impl (item) =>
+			Ok (Ident::new ("impl", item . impl_token . span ())),
+		Item::Macro (item) if is_macro_rules (item) =>
+			Ok (Ident::new ("macro", item . mac . bang_token . span ())),
+		Item::Mod (item) =>
+			Ok (Ident::new ("mod", item . mod_token . span ())),
+		Item::Static (item) =>
+			Ok (Ident::new ("static", item . static_token . span ())),
+		Item::Struct (item) =>
+		{
+			let struct_type = match item . fields
+			{
+				Fields::Named (_) => "struct",
+				Fields::Unnamed (_) => "tuple_struct",
+				Fields::Unit => "unit_struct"
+			};
+
+			Ok (Ident::new (struct_type, item . struct_token . span ()))
+		},
+		Item::Trait (item) =>
+		{
+			let trait_type =
+				if item . unsafety . is_some () { "unsafe_trait" }
+				else { "trait" };
+
+			Ok (Ident::new (trait_type, item . trait_token . span ()))
+		},
+		Item::TraitAlias (item) =>
+			Ok (Ident::new ("alias", item . trait_token . span ())),
+		Item::Type (item) =>
+			Ok (Ident::new ("type", item . type_token . span ())),
+		Item::Union (item) =>
+			Ok (Ident::new ("union", item . union_token . span ())),
+		Item::Use (item) =>
+			Ok (Ident::new ("use", item . use_token . span ())),
+		Item::Verbatim (_) => fn_signature (item)
+			. map (|signature| fn_type (&signature . sig))
+			. ok_or_else (|| Error::new_spanned (item, "Unsupported item")),
+		_ => Err (Error::new_spanned (item, "Unsupported item"))
+	}
+}
@@ -1,39 +1,473 @@
-use std::collections::HashSet;
 use std::fmt::{Display, Formatter, Result};
 
 use itertools::Itertools;
-use syn::{Ident, Token};
+use proc_macro2::TokenStream;
+use syn::{Ident, Item, Token, bracketed};
+use syn::token::Bracket;
 use syn::punctuated::Punctuated;
+use syn::parse::{Parse, ParseStream, Error};
 use syn::ext::IdentExt;
-use syn_derive::{Parse, ToTokens};
-use quote::TokenStreamExt;
+use syn_derive::ToTokens;
+use quote::ToTokens;
 
-#[derive (Parse, ToTokens, Debug)]
-pub struct ItemTypeSpec
+use crate::ItemConstraints;
+
+pub const ITEM_TYPES: &[&str] =
+&[
+	"alias",
+	"async_fn",
+	"const",
+	"const_fn",
+	"enum",
+	"extern",
+	"extern_block",
+	"field",
+	"fn",
+	"impl",
+	"macro",
+	"mod",
+	"static",
+	"struct",
+	"trait",
+	"trait_fn",
+	"tuple_struct",
+	"type",
+	"union",
+	"unit_struct",
+	"unsafe_trait",
+	"use",
+	"variant"
+];
+
+pub const ITEM_SUBTYPES: &[(&str, &str)] =
+&[
+	("async_fn", "fn"),
+	("const_fn", "fn"),
+	("tuple_struct", "struct"),
+	("unit_struct", "struct"),
+	("unsafe_trait", "trait")
+];
+
+pub const ITEM_TYPE_GROUPS: &[(&str, &[&str])] =
+&[
+	("adt", &["struct", "enum", "union"]),
+	("value", &["const", "static"])
+];
+
+pub const MEMBER_TYPES: &[(&str, &[&str])] =
+&[
+	("field", &["struct", "union"]),
+	("trait_fn", &["trait"]),
+	("variant", &["enum"])
+];
+
+fn member_parent_types (ty: &Ident) -> Option <&'static [&'static str]>
+{
+	MEMBER_TYPES
+		. iter ()
+		. find (|(member_type, _)| ty == member_type)
+		. map (|(_, parent_types)| *parent_types)
+}
+
+fn group_types (ty: &Ident) -> Option <&'static [&'static str]>
+{
+	ITEM_TYPE_GROUPS
+		. iter ()
+		. find (|(group, _)| ty == group)
+		. map (|(_, types)| *types)
+}
+
+fn is_subtype (found: &Ident, expected: &str) -> bool
+{
+	found == expected
+		|| ITEM_SUBTYPES
+			. iter ()
+			. any (|(subtype, parent)| found == subtype && *parent == expected)
+}
+
+fn base_types (ty: &Ident) -> Vec <&'static str>
+{
+	if let Some (types) = group_types (ty)
+	{
+		return types . to_vec ();
+	}
+
+	ITEM_SUBTYPES
+		. iter ()
+		. find (|(subtype, _)| ty == subtype)
+		. map (|(_, parent)| *parent)
+		. into_iter ()
+		. chain (ITEM_TYPES . iter () . copied () . filter (|known_type| ty == known_type))
+		. collect ()
+}
+
+fn parse_item_type_with_constraints (input: ParseStream <'_>)
+-> syn::Result <(Ident, ItemConstraints)>
 {
-	#[parse (
-		|input| Ok
+	let item_type = parse_item_type (input)?;
+	let constraints: ItemConstraints = input . parse ()?;
+
+	constraints . validate (&base_types (&item_type))?;
+
+	Ok ((item_type, constraints))
+}
+
+fn type_order (ty: &str) -> usize
+{
+	ITEM_TYPES
+		. iter ()
+		. position (|known_type| *known_type == ty)
+		. unwrap_or (ITEM_TYPES . len ())
+}
+
+fn parse_item_type (input: ParseStream <'_>) -> syn::Result <Ident>
+{
+	let item_type = Ident::parse_any (input)?;
+
+	if ! ITEM_TYPES . iter () . any (|known_type| item_type == known_type)
+		&& group_types (&item_type) . is_none ()
+	{
+		return Err
 		(
-			HashSet::from_iter
+			Error::new_spanned
 			(
-				Punctuated
-					::<Ident, Token! [|]>
-					::parse_separated_nonempty_with (input, Ident::parse_any)?
+				&item_type,
+				format!
+				(
+					"Unknown item type `{}`, expected one of {}",
+					item_type,
+					ITEM_TYPES
+						. iter ()
+						. chain (ITEM_TYPE_GROUPS . iter () . map (|(group, _)| group))
+						. format (", ")
+				)
 			)
-		)
-	)]
-	#[to_tokens (
-		|tokens, types: &HashSet <Ident>|
-		tokens . append_separated (types . iter (), <Token! [|]>::default ())
-	)]
-	pub types: HashSet <Ident>
+		);
+	}
+
+	Ok (item_type)
+}
+
+#[derive (Clone, Debug, ToTokens)]
+pub enum ItemTypeTerm
+{
+	Any (Token! [*]),
+	Not (Token! [!], Ident, ItemConstraints),
+	Is (Ident, ItemConstraints)
+}
+
+impl Parse for ItemTypeTerm
+{
+	fn parse (input: ParseStream <'_>) -> syn::Result <Self>
+	{
+		if input . peek (Token! [*])
+		{
+			Ok (Self::Any (input . parse ()?))
+		}
+		else if input . peek (Token! [!])
+		{
+			let bang_token = input . parse ()?;
+			let (ty, constraints) = parse_item_type_with_constraints (input)?;
+
+			Ok (Self::Not (bang_token, ty, constraints))
+		}
+		else
+		{
+			let (ty, constraints) = parse_item_type_with_constraints (input)?;
+
+			Ok (Self::Is (ty, constraints))
+		}
+	}
+}
+
+impl ItemTypeTerm
+{
+	fn matches (ty: &Ident, found: &Ident) -> bool
+	{
+		match group_types (ty)
+		{
+			Some (types) => types . iter () . any (|ty| is_subtype (found, ty)),
+			None => is_subtype (found, &ty . to_string ())
+		}
+	}
+
+	fn expanded_types ((ty, constraints): (&Ident, &ItemConstraints))
+	-> Vec <(String, String)>
+	{
+		match group_types (ty)
+		{
+			Some (types) => types
+				. iter ()
+				. map (|ty| (ty . to_string (), format! ("{}{}", ty, constraints)))
+				. collect (),
+			None => vec! [(ty . to_string (), format! ("{}{}", ty, constraints))]
+		}
+	}
+}
+
+#[derive (Clone, Debug)]
+pub struct ItemTypeSpec
+{
+	pub terms: Punctuated <ItemTypeTerm, Token! [|]>
+}
+
+impl Parse for ItemTypeSpec
+{
+	fn parse (input: ParseStream <'_>) -> syn::Result <Self>
+	{
+		// Specs travel between item macros in brackets so that macro_rules
+		// can pass them along as a single token tree.
+		if input . peek (Bracket)
+		{
+			let content;
+			bracketed! (content in input);
+
+			let terms = Punctuated::parse_separated_nonempty (&content)?;
+
+			if ! content . is_empty ()
+			{
+				return Err (content . error ("Unexpected tokens in item type spec"));
+			}
+
+			return Ok (Self {terms});
+		}
+
+		Ok (Self {terms: Punctuated::parse_separated_nonempty (input)?})
+	}
+}
+
+impl ToTokens for ItemTypeSpec
+{
+	fn to_tokens (&self, tokens: &mut TokenStream)
+	{
+		Bracket::default () . surround
+		(
+			tokens,
+			|inner_tokens| self . terms . to_tokens (inner_tokens)
+		);
+	}
 }
 
 impl ItemTypeSpec
 {
 	pub fn contains (&self, ty: &Ident) -> bool
 	{
-		self . types . contains (ty)
+		let mut has_positive_terms = false;
+		let mut positive_match = false;
+
+		for term in &self . terms
+		{
+			match term
+			{
+				ItemTypeTerm::Any (_) =>
+				{
+					has_positive_terms = true;
+					positive_match = true;
+				},
+				ItemTypeTerm::Not (_, negated, _) =>
+				{
+					if ItemTypeTerm::matches (negated, ty)
+					{
+						return false;
+					}
+				},
+				ItemTypeTerm::Is (expected, _) =>
+				{
+					has_positive_terms = true;
+					positive_match |= ItemTypeTerm::matches (expected, ty);
+				}
+			}
+		}
+
+		positive_match || ! has_positive_terms
+	}
+
+	pub fn has_constraints (&self) -> bool
+	{
+		self . terms . iter () . any
+		(
+			|term| match term
+			{
+				ItemTypeTerm::Any (_) => false,
+				ItemTypeTerm::Not (_, _, constraints)
+					| ItemTypeTerm::Is (_, constraints) => ! constraints . is_empty ()
+			}
+		)
+	}
+
+	pub fn check (&self, ty: &Ident, item: Option <&Item>, signature_only: bool)
+	-> std::result::Result <(), String>
+	{
+		let violation = |constraints: &ItemConstraints|
+			if signature_only && constraints . requires_full ()
+			{
+				Some (String::from ("only the signature was exported"))
+			}
+			else
+			{
+				item . and_then (|item| constraints . violation (item))
+			};
+
+		let mut has_positive_terms = false;
+		let mut positive_match = false;
+		let mut first_violation = None;
+
+		for term in &self . terms
+		{
+			match term
+			{
+				ItemTypeTerm::Any (_) =>
+				{
+					has_positive_terms = true;
+					positive_match = true;
+				},
+				ItemTypeTerm::Not (_, negated, constraints) =>
+				{
+					if ItemTypeTerm::matches (negated, ty)
+						&& violation (constraints) . is_none ()
+					{
+						return Err
+						(
+							format! ("Expected item of type {}, found {}", self, ty)
+						);
+					}
+				},
+				ItemTypeTerm::Is (expected, constraints) =>
+				{
+					has_positive_terms = true;
+
+					if ItemTypeTerm::matches (expected, ty)
+					{
+						match violation (constraints)
+						{
+							Some (violation) =>
+							{
+								first_violation . get_or_insert (violation);
+							},
+							None => positive_match = true
+						}
+					}
+				}
+			}
+		}
+
+		if positive_match || ! has_positive_terms
+		{
+			return Ok (());
+		}
+
+		match first_violation
+		{
+			Some (violation) =>
+				Err (format! ("Expected item of type {}: {}", self, violation)),
+			None => Err (format! ("Expected item of type {}, found {}", self, ty))
+		}
+	}
+
+	pub fn names (&self, ty: &str) -> bool
+	{
+		self
+			. terms
+			. iter ()
+			. any (|term| matches! (term, ItemTypeTerm::Is (name, _) if name == ty))
+	}
+
+	pub fn selects_member (&self) -> bool
+	{
+		self . terms . iter () . any
+		(
+			|term| matches!
+			(
+				term,
+				ItemTypeTerm::Is (ty, _) | ItemTypeTerm::Not (_, ty, _)
+					if member_parent_types (ty) . is_some ()
+			)
+		)
+	}
+
+	pub fn validate_member_selection (&self) -> syn::Result <()>
+	{
+		if ! self . selects_member ()
+		{
+			return Ok (());
+		}
+
+		for term in &self . terms
+		{
+			match term
+			{
+				ItemTypeTerm::Is (ty, _) if member_parent_types (ty) . is_some () => {},
+				ItemTypeTerm::Not (_, ty, _) if member_parent_types (ty) . is_some () =>
+					return Err
+					(
+						Error::new_spanned
+						(
+							term,
+							format! ("Member type `{}` cannot be negated", ty)
+						)
+					),
+				_ => return Err
+				(
+					Error::new_spanned
+					(
+						term,
+						format!
+						(
+							"Cannot select a member and a whole item of type `{}` with the same path",
+							term . to_token_stream ()
+						)
+					)
+				)
+			}
+		}
+
+		Ok (())
+	}
+
+	pub fn parent_spec (&self) -> Self
+	{
+		let mut terms = Punctuated::new ();
+
+		for term in &self . terms
+		{
+			match term
+			{
+				ItemTypeTerm::Is (ty, _) => match member_parent_types (ty)
+				{
+					Some (parent_types) => terms . extend
+					(
+						parent_types
+							. iter ()
+							. map
+							(
+								|parent_type| ItemTypeTerm::Is
+								(
+									Ident::new (parent_type, ty . span ()),
+									ItemConstraints::default ()
+								)
+							)
+					),
+					None => terms . push (term . clone ())
+				},
+				_ => terms . push (term . clone ())
+			}
+		}
+
+		Self {terms}
+	}
+
+	fn expanded_types <F> (&self, filter: F) -> Vec <String>
+	where F: Fn (&ItemTypeTerm) -> Option <(&Ident, &ItemConstraints)>
+	{
+		self
+			. terms
+			. iter ()
+			. filter_map (filter)
+			. flat_map (ItemTypeTerm::expanded_types)
+			. unique ()
+			. sorted_by_key (|(ty, _)| type_order (ty))
+			. map (|(_, display)| display)
+			. collect ()
 	}
 }
 
@@ -41,17 +475,44 @@ impl Display for ItemTypeSpec
 {
 	fn fmt (&self, f: &mut Formatter <'_>) -> Result
 	{
-		match self . types . len ()
-		{
-			0 =>
+		let any = self
+			. terms
+			. iter ()
+			. any (|term| matches! (term, ItemTypeTerm::Any (_)));
+
+		let allowed_types = self . expanded_types
+		(
+			|term| match term
 			{
-				write! (f, "nothing")
-			},
-			_ =>
+				ItemTypeTerm::Is (ty, constraints) => Some ((ty, constraints)),
+				_ => None
+			}
+		);
+
+		let excluded_types = self . expanded_types
+		(
+			|term| match term
 			{
-				write! (f, "{}", self . types . iter () . format (", "))
+				ItemTypeTerm::Not (_, ty, constraints) => Some ((ty, constraints)),
+				_ => None
 			}
+		);
+
+		if any || allowed_types . is_empty ()
+		{
+			write! (f, "any item")?;
+		}
+		else
+		{
+			write! (f, "{}", allowed_types . iter () . format (", "))?;
 		}
+
+		if ! excluded_types . is_empty ()
+		{
+			write! (f, " except {}", excluded_types . iter () . format (", "))?;
+		}
+
+		Ok (())
 	}
 }
 
@@ -1,22 +1,70 @@
+pub mod generics;
+pub mod path_utils;
+pub mod substitute;
+pub mod testing;
+
 mod sanitize;
-use sanitize::*;
+pub use sanitize::{
+	sanitize,
+	desanitize,
+	defining_crate_placeholder,
+	reject_defining_crate_placeholder,
+	expand_defining_crate
+};
+
+mod protocol;
+pub use protocol::{PROTOCOL_VERSION, ProtocolHeader};
+
+mod item_constraints;
+pub use item_constraints::{ItemConstraints, GenericArity, ItemShape};
+
+mod item_type;
+pub use item_type::{item_type, is_macro_rules};
 
 mod item_type_spec;
-pub use item_type_spec::{ItemTypeSpec, ItemTypeMismatch};
+pub use item_type_spec::{ItemTypeSpec, ItemTypeTerm, ItemTypeMismatch};
 
 mod item_argument;
-pub use item_argument::ItemArgument;
+pub use item_argument::{ItemArgument, FieldSelector};
+
+mod instantiate_item;
+pub use instantiate_item::instantiate_item;
+
+mod select_member;
+pub use select_member::{SelectedMember, select_member};
+
+mod fetched_item;
+pub use fetched_item::FetchedItem;
 
 mod multi_item_macro_input;
 pub use multi_item_macro_input::MultiItemMacroInput;
 
+mod fn_signature;
+pub use fn_signature::{
+	fn_signature,
+	strip_fn_body,
+	strip_impl_fn_body,
+	fn_with_empty_body,
+	impl_fn_with_empty_body
+};
+
+mod strip_item;
+pub use strip_item::ItemStripper;
 
+mod item_hash;
+pub use item_hash::item_hash;
 
 mod get_macro;
-pub use get_macro::{get_macro_ident, get_macro_path};
+pub use get_macro::{get_macro_ident, get_mangled_macro_ident, get_macro_path};
 
 mod generate_item_macro;
 pub use generate_item_macro::generate_item_macro;
 
 mod generate_macrospace_invokation;
 pub use generate_macrospace_invokation::generate_macrospace_invokation;
+
+mod invocation;
+pub use invocation::Invocation;
+
+mod consumer;
+pub use consumer::{ConsumerData, invoke_attribute_consumer, invoke_derive_consumer};
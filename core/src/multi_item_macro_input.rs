@@ -1,14 +1,87 @@
 use proc_macro2::TokenStream;
-use syn::{braced, bracketed, parse2};
+use syn::{Token, braced, bracketed, parse2};
+use syn::token::{Bracket, Paren};
+use syn::punctuated::Punctuated;
 use syn::parse::{Parse, ParseStream, Result};
 use quote::ToTokens;
 
-use crate::{sanitize, desanitize};
+use crate::{
+	ItemArgument,
+	FetchedItem,
+	sanitize,
+	desanitize,
+	instantiate_item,
+	select_member
+};
+
+fn fetch_items (input: ParseStream <'_>) -> Result <(TokenStream, Vec <FetchedItem>)>
+{
+	let item_arguments: Vec <ItemArgument> =
+		if input . peek (Bracket)
+		{
+			let content;
+			bracketed! (content in input);
+
+			Punctuated::<ItemArgument, Token! [,]>::parse_terminated (&content)?
+				. into_iter ()
+				. collect ()
+		}
+		else { Vec::new () };
+
+	if ! input . peek (Paren)
+	{
+		return Ok ((desanitize (input . parse::<TokenStream> ()?), Vec::new ()));
+	}
+
+	let mut items_tokens = TokenStream::new ();
+	let mut items = Vec::new ();
+	let mut item_arguments = item_arguments . iter ();
+
+	while ! input . is_empty ()
+	{
+		// Only the item itself was sanitized, the request list and the header
+		// in front of each item are passed through as they were written.
+		let mut fetched_item: FetchedItem = input . parse ()?;
+		fetched_item . item = parse2 (desanitize (&fetched_item . item))?;
+
+		match item_arguments . next ()
+		{
+			Some (item_argument) =>
+			{
+				if item_argument . instantiate_token . is_some ()
+				{
+					fetched_item . item =
+						instantiate_item (item_argument, fetched_item . item)?;
+				}
+
+				if item_argument . type_spec . selects_member ()
+				{
+					let member = select_member (item_argument, &fetched_item . item)?;
+					member . to_tokens (&mut items_tokens);
+
+					fetched_item . path = item_argument . path . clone ();
+					fetched_item . kind = member . kind ();
+					fetched_item . member = Some (member);
+				}
+				else
+				{
+					fetched_item . item . to_tokens (&mut items_tokens);
+				}
+			},
+			None => fetched_item . item . to_tokens (&mut items_tokens)
+		}
+
+		items . push (fetched_item);
+	}
+
+	Ok ((items_tokens, items))
+}
 
 pub struct MultiItemMacroInput <T>
 {
 	pub brace_token: syn::token::Brace,
 	pub items_tokens: TokenStream,
+	pub items: Vec <FetchedItem>,
 	pub bracket_token: syn::token::Bracket,
 	pub user_data: T
 }
@@ -20,9 +93,7 @@ where T: Parse
 	{
 		let content;
 		let brace_token = braced! (content in input);
-		let sanitized_items_tokens: TokenStream = content . parse ()?;
-
-		let items_tokens = desanitize (sanitized_items_tokens);
+		let (items_tokens, items) = fetch_items (&content)?;
 
 		let content;
 		let bracket_token = bracketed! (content in input);
@@ -30,7 +101,7 @@ where T: Parse
 
 		let user_data = parse2 (desanitize (sanitized_user_data))?;
 
-		Ok (Self {brace_token, items_tokens, bracket_token, user_data})
+		Ok (Self {brace_token, items_tokens, items, bracket_token, user_data})
 	}
 }
 
@@ -0,0 +1,474 @@
+use std::collections::HashMap;
+
+use syn::{
+	Ident,
+	Path,
+	PathSegment,
+	PathArguments,
+	AngleBracketedGenericArguments,
+	GenericArgument,
+	Item,
+	ItemUse,
+	ImplItem,
+	Block,
+	Expr,
+	UseTree,
+	Visibility,
+	Token
+};
+use syn::punctuated::Punctuated;
+use syn::fold::{Fold, fold_path, fold_item, fold_impl_item};
+use syn::parse::{Result, Error};
+use quote::ToTokens;
+
+use crate::{
+	fn_signature,
+	strip_fn_body,
+	strip_impl_fn_body,
+	fn_with_empty_body,
+	impl_fn_with_empty_body
+};
+
+pub fn get_path_arguments (path: &Path)
+-> Result <Option <&Punctuated <GenericArgument, Token! [,]>>>
+{
+	path
+		. segments
+		. last ()
+		. ok_or (Error::new_spanned (path, "Path must be nonempty"))
+		. and_then
+		(
+			|last_segment| match &last_segment . arguments
+			{
+				PathArguments::AngleBracketed (arguments) =>
+					Ok (Some (&arguments . args)),
+				PathArguments::Parenthesized (_) => Err
+				(
+					Error::new_spanned
+					(
+						path,
+						"Parenthesized path arguments are not supported"
+					)
+				),
+				_ => Ok (None)
+			}
+		)
+}
+
+pub fn get_path_arguments_mut (path: &mut Path)
+-> Result <Option <&mut Punctuated <GenericArgument, Token! [,]>>>
+{
+	let path_tokens = path . to_token_stream ();
+
+	match path . segments . last_mut ()
+	{
+		Some (last_segment) => match &mut last_segment . arguments
+		{
+			PathArguments::AngleBracketed (arguments) =>
+				Ok (Some (&mut arguments . args)),
+			PathArguments::Parenthesized (_) => Err
+			(
+				Error::new_spanned
+				(
+					path_tokens,
+					"Parenthesized path arguments are not supported"
+				)
+			),
+			_ => Ok (None)
+		},
+		None => Err (Error::new_spanned (path_tokens, "Path must be nonempty"))
+	}
+}
+
+pub fn make_path_arguments (path: &mut Path)
+-> Result <&mut Punctuated <GenericArgument, Token! [,]>>
+{
+	let path_tokens = path . to_token_stream ();
+
+	match path . segments . last_mut ()
+	{
+		Some (last_segment) =>
+		{
+			let path_arguments = match &mut last_segment . arguments
+			{
+				PathArguments::AngleBracketed (arguments) => return Ok (&mut arguments . args),
+				PathArguments::Parenthesized (_) => return Err
+				(
+					Error::new_spanned
+					(
+						path_tokens,
+						"Parenthesized path arguments are not supported"
+					)
+				),
+				a @ _ => a
+			};
+
+			*path_arguments = PathArguments::AngleBracketed
+			(
+				AngleBracketedGenericArguments
+				{
+					colon2_token: None,
+					lt_token: Default::default (),
+					args: Punctuated::new (),
+					gt_token: Default::default ()
+				}
+			);
+
+			if let PathArguments::AngleBracketed (arguments) = path_arguments
+			{
+				Ok (&mut arguments . args)
+			}
+			else
+			{
+				unreachable! ();
+			}
+		},
+		None => Err (Error::new_spanned (path_tokens, "Path must be nonempty"))
+	}
+}
+
+pub fn as_prefix (mut p: Path) -> Path
+{
+	if let Some (last_segment) = p . segments . last_mut ()
+	{
+		if let PathArguments::AngleBracketed (path_arguments) =
+			&mut last_segment . arguments
+		{
+			path_arguments
+				. colon2_token
+				. get_or_insert (<Token! [::]>::default ());
+		}
+	}
+
+	p
+}
+
+pub fn without_arguments (mut p: Path) -> Path
+{
+	if let Some (last_segment) = p . segments . last_mut ()
+	{
+		last_segment . arguments = PathArguments::None;
+	}
+
+	p
+}
+
+pub fn join_paths (prefix: &Path, suffix: impl IntoIterator <Item = PathSegment>)
+-> Path
+{
+	let mut joined_path = prefix . clone ();
+	joined_path . segments . extend (suffix);
+	joined_path
+}
+
+#[derive (Clone)]
+pub struct PathResolver
+{
+	pub crate_path: Path,
+	pub module_path: Option <Vec <Ident>>,
+	pub imports: HashMap <Ident, Path>,
+	pub self_path: Option <Path>
+}
+
+impl PathResolver
+{
+	pub fn new (crate_path: Path) -> Self
+	{
+		Self
+		{
+			crate_path,
+			module_path: None,
+			imports: HashMap::new (),
+			self_path: None
+		}
+	}
+
+	pub fn with_module_path <I> (mut self, module_path: I) -> Self
+	where I: IntoIterator <Item = Ident>
+	{
+		self . module_path = Some (module_path . into_iter () . collect ());
+		self
+	}
+
+	pub fn with_self_path (mut self, self_path: Path) -> Self
+	{
+		self . self_path = Some (self_path);
+		self
+	}
+
+	pub fn module_prefix (&self) -> Option <Path>
+	{
+		let module_path = self . module_path . as_ref ()?;
+
+		Some
+		(
+			join_paths
+			(
+				&self . crate_path,
+				module_path . iter () . cloned () . map (PathSegment::from)
+			)
+		)
+	}
+
+	fn add_use_tree (&mut self, prefix: Path, use_tree: &UseTree)
+	{
+		match use_tree
+		{
+			UseTree::Path (use_path) =>
+			{
+				let mut prefix = prefix;
+				prefix . segments . push (use_path . ident . clone () . into ());
+				self . add_use_tree (prefix, &use_path . tree);
+			},
+			UseTree::Name (use_name) if use_name . ident == "self" =>
+				if let Some (last_segment) = prefix . segments . last ()
+			{
+				let ident = last_segment . ident . clone ();
+				self . add_import (ident, prefix);
+			},
+			UseTree::Name (use_name) =>
+			{
+				let mut path = prefix;
+				path . segments . push (use_name . ident . clone () . into ());
+				self . add_import (use_name . ident . clone (), path);
+			},
+			UseTree::Rename (use_rename) =>
+			{
+				let mut path = prefix;
+				path . segments . push (use_rename . ident . clone () . into ());
+				self . add_import (use_rename . rename . clone (), path);
+			},
+			UseTree::Group (use_group) =>
+				for use_tree in &use_group . items
+			{
+				self . add_use_tree (prefix . clone (), use_tree);
+			},
+			UseTree::Glob (_) => {}
+		}
+	}
+
+	fn add_import (&mut self, ident: Ident, path: Path)
+	{
+		let path = self . resolve_path (path);
+		self . imports . insert (ident, path);
+	}
+
+	pub fn add_use (&mut self, item_use: &ItemUse)
+	{
+		let prefix = Path
+		{
+			leading_colon: item_use . leading_colon,
+			segments: Punctuated::new ()
+		};
+
+		self . add_use_tree (prefix, &item_use . tree);
+	}
+
+	pub fn resolve_path (&self, path: Path) -> Path
+	{
+		if path . leading_colon . is_some ()
+		{
+			return path;
+		}
+
+		let Some (first_segment) = path . segments . first ()
+		else
+		{
+			return path;
+		};
+
+		let num_supers = path
+			. segments
+			. iter ()
+			. take_while (|segment| segment . ident == "super")
+			. count ();
+
+		if first_segment . ident == "crate"
+		{
+			join_paths (&self . crate_path, path . segments . into_iter () . skip (1))
+		}
+		else if first_segment . ident == "self" && path . segments . len () > 1
+		{
+			match self . module_prefix ()
+			{
+				Some (module_prefix) =>
+					join_paths (&module_prefix, path . segments . into_iter () . skip (1)),
+				None => path
+			}
+		}
+		else if num_supers > 0
+		{
+			match &self . module_path
+			{
+				Some (module_path) if num_supers <= module_path . len () => join_paths
+				(
+					&self . crate_path,
+					module_path
+						. iter ()
+						. take (module_path . len () - num_supers)
+						. cloned ()
+						. map (PathSegment::from)
+						. chain (path . segments . into_iter () . skip (num_supers))
+				),
+				_ => path
+			}
+		}
+		else if first_segment . ident == "Self"
+		{
+			match &self . self_path
+			{
+				Some (self_path) => join_paths
+				(
+					&as_prefix (self_path . clone ()),
+					path . segments . into_iter () . skip (1)
+				),
+				None => path
+			}
+		}
+		else if let Some (import_path) = self . imports . get (&first_segment . ident)
+		{
+			let mut segments = path . segments . into_iter ();
+			let first_segment = segments . next ();
+			let mut resolved_path = import_path . clone ();
+
+			if let (Some (first_segment), Some (last_segment))
+				= (first_segment, resolved_path . segments . last_mut ())
+			{
+				last_segment . arguments = first_segment . arguments;
+			}
+
+			join_paths (&resolved_path, segments)
+		}
+		else
+		{
+			path
+		}
+	}
+
+	// Paths relative to the current module would silently mean something else
+	// wherever the item is expanded, so they are errors if they stay relative.
+	// `try_fold_item` only applies this outside of expressions and blocks,
+	// which are left as resolved as they can be.
+	pub fn try_resolve_path (&self, path: Path) -> Result <Path>
+	{
+		let resolved_path = self . resolve_path (path);
+
+		let relative_segment = resolved_path
+			. segments
+			. first ()
+			. filter (|_| resolved_path . leading_colon . is_none ())
+			. map (|segment| &segment . ident)
+			. filter
+			(
+				|ident| *ident == "super"
+					|| *ident == "self" && resolved_path . segments . len () > 1
+			);
+
+		match (relative_segment, &self . module_path)
+		{
+			(Some (ident), None) => Err
+			(
+				Error::new_spanned
+				(
+					&resolved_path,
+					format! ("`{}` paths can only be resolved with `module = ...`", ident)
+				)
+			),
+			(Some (_), Some (_)) => Err
+			(
+				Error::new_spanned (&resolved_path, "`super` path goes above the crate root")
+			),
+			(None, _) => Ok (resolved_path)
+		}
+	}
+
+	pub fn try_fold_item (&self, item: Item) -> Result <Item>
+	{
+		let mut resolving_fold = TryResolvingFold {resolver: self, error: None};
+		let item = resolving_fold . fold_item (item);
+
+		match resolving_fold . error
+		{
+			Some (error) => Err (error),
+			None => Ok (item)
+		}
+	}
+}
+
+struct TryResolvingFold <'a>
+{
+	resolver: &'a PathResolver,
+	error: Option <Error>
+}
+
+// Items are stripped before they are resolved, so stripped fns are given an
+// empty body to have their signatures resolved, then stripped again.
+impl Fold for TryResolvingFold <'_>
+{
+	fn fold_item (&mut self, node: Item) -> Item
+	{
+		match fn_signature (&node)
+		{
+			Some (signature) =>
+				strip_fn_body (self . fold_item_fn (fn_with_empty_body (signature))),
+			None => fold_item (self, node)
+		}
+	}
+
+	fn fold_impl_item (&mut self, node: ImplItem) -> ImplItem
+	{
+		match impl_fn_with_empty_body (&node)
+		{
+			Some (impl_fn) => strip_impl_fn_body (self . fold_impl_item_fn (impl_fn)),
+			None => fold_impl_item (self, node)
+		}
+	}
+
+	fn fold_path (&mut self, node: Path) -> Path
+	{
+		match self . resolver . try_resolve_path (node . clone ())
+		{
+			Ok (resolved_path) => fold_path (self, resolved_path),
+			Err (error) =>
+			{
+				match &mut self . error
+				{
+					Some (first_error) => first_error . combine (error),
+					None => self . error = Some (error)
+				}
+
+				node
+			}
+		}
+	}
+
+	fn fold_block (&mut self, node: Block) -> Block
+	{
+		self . resolver . clone () . fold_block (node)
+	}
+
+	fn fold_expr (&mut self, node: Expr) -> Expr
+	{
+		self . resolver . clone () . fold_expr (node)
+	}
+
+	fn fold_visibility (&mut self, node: Visibility) -> Visibility
+	{
+		node
+	}
+}
+
+impl Fold for PathResolver
+{
+	fn fold_path (&mut self, node: Path) -> Path
+	{
+		let resolved_path = self . resolve_path (node);
+
+		fold_path (self, resolved_path)
+	}
+
+	fn fold_visibility (&mut self, node: Visibility) -> Visibility
+	{
+		node
+	}
+}
@@ -0,0 +1,86 @@
+use syn::{LitInt, Token};
+use syn::parse::{Parse, ParseStream, Result, Error};
+use syn_derive::ToTokens;
+use proc_macro2::Span;
+
+pub const PROTOCOL_VERSION: u32 = 2;
+
+pub mod kw
+{
+	syn::custom_keyword! (macrospace_protocol);
+}
+
+#[derive (Clone, ToTokens)]
+pub struct ProtocolHeader
+{
+	pub at_token: Token! [@],
+	pub protocol_token: kw::macrospace_protocol,
+	pub version: LitInt
+}
+
+impl ProtocolHeader
+{
+	pub fn new () -> Self
+	{
+		Self
+		{
+			at_token: <Token! [@]>::default (),
+			protocol_token: kw::macrospace_protocol::default (),
+			version: LitInt::new (&PROTOCOL_VERSION . to_string (), Span::call_site ())
+		}
+	}
+}
+
+impl Default for ProtocolHeader
+{
+	fn default () -> Self
+	{
+		Self::new ()
+	}
+}
+
+impl Parse for ProtocolHeader
+{
+	fn parse (input: ParseStream <'_>) -> Result <Self>
+	{
+		if ! input . peek (Token! [@]) || ! input . peek2 (kw::macrospace_protocol)
+		{
+			return Err
+			(
+				input . error
+				(
+					format!
+					(
+						"Missing macrospace protocol header, expected protocol version {}; \
+						the item was probably exported by an older version of macrospace",
+						PROTOCOL_VERSION
+					)
+				)
+			);
+		}
+
+		let at_token = input . parse ()?;
+		let protocol_token = input . parse ()?;
+		let version: LitInt = input . parse ()?;
+
+		if version . base10_parse::<u32> ()? != PROTOCOL_VERSION
+		{
+			return Err
+			(
+				Error::new_spanned
+				(
+					&version,
+					format!
+					(
+						"macrospace protocol version mismatch: expected {}, found {}; \
+						make sure that all crates use the same version of macrospace",
+						PROTOCOL_VERSION,
+						version
+					)
+				)
+			);
+		}
+
+		Ok (Self {at_token, protocol_token, version})
+	}
+}
@@ -1,8 +1,23 @@
-use proc_macro2::{TokenStream, TokenTree, Ident, Punct, Group, Spacing};
+use proc_macro2::{TokenStream, TokenTree, Ident, Punct, Group, Spacing, Span};
+use syn::parse::{Result, Error};
 use quote::{ToTokens, TokenStreamExt};
 
 const DOLLAR_ALONE: &str = "__sanitized_dollar_token_alone__";
 const DOLLAR_JOINT: &str = "__sanitized_dollar_token_joint__";
+const ESCAPE_PREFIX: &str = "__sanitized_escaped_";
+const DEFINING_CRATE: &str = "__macrospace_defining_crate__";
+
+// Idents that could be mistaken for a sentinel or for the defining crate
+// placeholder, including ones that were escaped by an earlier round, gain one
+// more escape prefix.  Desanitizing strips exactly one prefix, so any number
+// of nested rounds round-trips.
+fn is_reserved (ident: &str) -> bool
+{
+	ident == DOLLAR_ALONE
+		|| ident == DOLLAR_JOINT
+		|| ident == DEFINING_CRATE
+		|| ident . starts_with (ESCAPE_PREFIX)
+}
 
 pub fn sanitize <T> (raw_tokens: T) -> TokenStream
 where T: ToTokens
@@ -37,6 +52,13 @@ where T: ToTokens
 				sanitized_group . set_span (group . span ());
 				sanitized_tokens . append (sanitized_group);
 			},
+			TokenTree::Ident (ident) if is_reserved (&ident . to_string ()) =>
+			{
+				sanitized_tokens . append
+				(
+					Ident::new (&format! ("{}{}", ESCAPE_PREFIX, ident), ident . span ())
+				);
+			},
 			t @ _ => sanitized_tokens . append (t)
 		}
 	}
@@ -67,6 +89,10 @@ where T: ToTokens
 					dollar_token . set_span (ident . span ());
 					desanitized_tokens . append (dollar_token);
 				}
+				else if let Some (escaped) = ident . to_string () . strip_prefix (ESCAPE_PREFIX)
+				{
+					desanitized_tokens . append (Ident::new (escaped, ident . span ()));
+				}
 				else
 				{
 					desanitized_tokens . append (ident);
@@ -88,3 +114,228 @@ where T: ToTokens
 
 	desanitized_tokens
 }
+
+pub fn defining_crate_placeholder () -> Ident
+{
+	Ident::new (DEFINING_CRATE, Span::call_site ())
+}
+
+// The placeholder can only stand for the defining crate if the item did not
+// already spell it out, since both would be indistinguishable once resolved.
+pub fn reject_defining_crate_placeholder <T> (raw_tokens: T) -> Result <()>
+where T: ToTokens
+{
+	for token in raw_tokens . into_token_stream ()
+	{
+		match token
+		{
+			TokenTree::Ident (ident) if ident == DEFINING_CRATE => return Err
+			(
+				Error::new
+				(
+					ident . span (),
+					format! ("`{}` is reserved by macrospace", DEFINING_CRATE)
+				)
+			),
+			TokenTree::Group (group) => reject_defining_crate_placeholder (group . stream ())?,
+			_ => {}
+		}
+	}
+
+	Ok (())
+}
+
+// Sanitizing escapes the placeholder like any other reserved ident, so it is
+// the escaped placeholder that is swapped for `$crate`, leaving it to survive
+// as a real `$crate` in the generated item macro.
+pub fn expand_defining_crate <T> (sanitized_tokens: T) -> TokenStream
+where T: ToTokens
+{
+	let escaped_placeholder = format! ("{}{}", ESCAPE_PREFIX, DEFINING_CRATE);
+	let mut expanded_tokens = TokenStream::new ();
+
+	for token in sanitized_tokens . into_token_stream ()
+	{
+		match token
+		{
+			TokenTree::Ident (ident) if ident == escaped_placeholder =>
+			{
+				let mut dollar_token = Punct::new ('$', Spacing::Alone);
+				dollar_token . set_span (ident . span ());
+				expanded_tokens . append (dollar_token);
+				expanded_tokens . append
+				(
+					Ident::new ("crate", ident . span ())
+				);
+			},
+			TokenTree::Group (group) =>
+			{
+				let mut expanded_group = Group::new
+				(
+					group . delimiter (),
+					expand_defining_crate (group . stream ())
+				);
+				expanded_group . set_span (group . span ());
+				expanded_tokens . append (expanded_group);
+			},
+			t => expanded_tokens . append (t)
+		}
+	}
+
+	expanded_tokens
+}
+
+#[cfg (test)]
+mod tests
+{
+	use proc_macro2::{TokenStream, TokenTree, Ident, Punct, Group, Delimiter, Spacing, Span};
+	use quote::{TokenStreamExt, quote};
+
+	use super::*;
+
+	const SENTINELS: [&str; 4] = [DOLLAR_ALONE, DOLLAR_JOINT, ESCAPE_PREFIX, DEFINING_CRATE];
+
+	// `to_string` does not show spacing, which matters for `$`.
+	fn describe (tokens: TokenStream) -> Vec <String>
+	{
+		tokens
+			. into_iter ()
+			. map
+			(
+				|token| match token
+				{
+					TokenTree::Group (group) => format!
+					(
+						"{:?}{:?}",
+						group . delimiter (),
+						describe (group . stream ())
+					),
+					TokenTree::Punct (punct) => format!
+					(
+						"{}{:?}",
+						punct . as_char (),
+						punct . spacing ()
+					),
+					token => token . to_string ()
+				}
+			)
+			. collect ()
+	}
+
+	// Every sentinel name, escaped any number of times, next to real dollar
+	// tokens of both spacings, at the top level and inside groups.
+	fn inputs () -> Vec <TokenStream>
+	{
+		let mut inputs = Vec::new ();
+
+		for sentinel in SENTINELS
+		{
+			for escapes in 0 .. 3
+			{
+				let name = format! ("{}{}", ESCAPE_PREFIX . repeat (escapes), sentinel);
+				let ident = Ident::new (&name, Span::call_site ());
+
+				let mut tokens = TokenStream::new ();
+				tokens . append (Punct::new ('$', Spacing::Joint));
+				tokens . append (ident . clone ());
+				tokens . append (Punct::new ('$', Spacing::Alone));
+				tokens . extend (quote! (x #ident :: y));
+
+				let group = Group::new (Delimiter::Brace, tokens . clone ());
+				tokens . append (Group::new (Delimiter::Parenthesis, quote! (#group)));
+
+				inputs . push (tokens);
+			}
+		}
+
+		inputs . push
+		(
+			SENTINELS
+				. iter ()
+				. map (|name| TokenTree::from (Ident::new (name, Span::call_site ())))
+				. collect ()
+		);
+
+		inputs
+	}
+
+	fn contains_dollar (tokens: TokenStream) -> bool
+	{
+		tokens . into_iter () . any
+		(
+			|token| match token
+			{
+				TokenTree::Punct (punct) => punct . as_char () == '$',
+				TokenTree::Group (group) => contains_dollar (group . stream ()),
+				_ => false
+			}
+		)
+	}
+
+	#[test]
+	fn nested_rounds_round_trip ()
+	{
+		for input in inputs ()
+		{
+			for depth in 1 ..= 4
+			{
+				let mut tokens = input . clone ();
+
+				for _ in 0 .. depth
+				{
+					tokens = sanitize (tokens);
+					assert! (! contains_dollar (tokens . clone ()), "{}", tokens);
+				}
+
+				for _ in 0 .. depth
+				{
+					tokens = desanitize (tokens);
+				}
+
+				assert_eq! (describe (tokens), describe (input . clone ()), "depth {}", depth);
+			}
+		}
+	}
+
+	#[test]
+	fn sanitized_placeholder_is_escaped ()
+	{
+		let placeholder = defining_crate_placeholder ();
+		let sanitized = sanitize (quote! (#placeholder :: Foo));
+
+		assert_eq!
+		(
+			describe (sanitized . clone ()),
+			describe (quote! (__sanitized_escaped___macrospace_defining_crate__ :: Foo))
+		);
+		assert_eq! (describe (desanitize (sanitized)), describe (quote! (#placeholder :: Foo)));
+	}
+
+	#[test]
+	fn only_the_placeholder_becomes_dollar_crate ()
+	{
+		let placeholder = defining_crate_placeholder ();
+		let escaped_placeholder = Ident::new
+		(
+			&format! ("{}{}", ESCAPE_PREFIX, DEFINING_CRATE),
+			Span::call_site ()
+		);
+
+		let expanded = expand_defining_crate (sanitize (quote! (#placeholder #escaped_placeholder)));
+
+		assert_eq!
+		(
+			describe (desanitize (expanded)),
+			describe (quote! ($crate #escaped_placeholder))
+		);
+	}
+
+	#[test]
+	fn written_out_placeholder_is_rejected ()
+	{
+		let placeholder = defining_crate_placeholder ();
+
+		assert! (reject_defining_crate_placeholder (quote! (struct A (#placeholder::B);)) . is_err ());
+		assert! (reject_defining_crate_placeholder (quote! (struct A (crate::B);)) . is_ok ());
+	}
+}
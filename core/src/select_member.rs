@@ -0,0 +1,135 @@
+use proc_macro2::TokenStream;
+use syn::{Ident, Item, TraitItem, TraitItemFn, Field, Variant, Member};
+use syn::parse::{Result, Error};
+use syn::spanned::Spanned;
+use quote::ToTokens;
+
+use crate::ItemArgument;
+
+#[derive (Clone)]
+pub enum SelectedMember
+{
+	Field (Field),
+	TraitFn (TraitItemFn),
+	Variant (Variant)
+}
+
+impl SelectedMember
+{
+	pub fn kind (&self) -> Ident
+	{
+		match self
+		{
+			Self::Field (field) => Ident::new ("field", field . span ()),
+			Self::TraitFn (trait_fn) => Ident::new ("trait_fn", trait_fn . sig . fn_token . span),
+			Self::Variant (variant) => Ident::new ("variant", variant . ident . span ())
+		}
+	}
+}
+
+impl ToTokens for SelectedMember
+{
+	fn to_tokens (&self, tokens: &mut TokenStream)
+	{
+		match self
+		{
+			Self::Field (field) => field . to_tokens (tokens),
+			Self::TraitFn (trait_fn) => trait_fn . to_tokens (tokens),
+			Self::Variant (variant) => variant . to_tokens (tokens)
+		}
+	}
+}
+
+pub fn select_member (item_argument: &ItemArgument, item: &Item)
+-> Result <SelectedMember>
+{
+	let member_ident = item_argument
+		. path
+		. segments
+		. last ()
+		. map (|segment| &segment . ident);
+
+	let selected = match (item, &item_argument . field_selector, member_ident)
+	{
+		(Item::Struct (item_struct), Some (field_selector), _) => item_struct
+			. fields
+			. iter ()
+			. enumerate ()
+			. find
+			(
+				|(i, field)| match &field . ident
+				{
+					Some (ident) => Member::Named (ident . clone ()),
+					None => Member::Unnamed ((*i) . into ())
+				}
+					== field_selector . member
+			)
+			. map (|(_, field)| SelectedMember::Field (field . clone ())),
+		(Item::Union (item_union), Some (field_selector), _) => item_union
+			. fields
+			. named
+			. iter ()
+			. find
+			(
+				|field| field
+					. ident
+					. clone ()
+					. map (Member::Named)
+					. is_some_and (|member| member == field_selector . member)
+			)
+			. map (|field| SelectedMember::Field (field . clone ())),
+		(Item::Trait (item_trait), None, Some (member_ident)) => item_trait
+			. items
+			. iter ()
+			. find_map
+			(
+				|trait_item| match trait_item
+				{
+					TraitItem::Fn (trait_fn) if trait_fn . sig . ident == *member_ident =>
+						Some (SelectedMember::TraitFn (trait_fn . clone ())),
+					_ => None
+				}
+			),
+		(Item::Enum (item_enum), None, Some (member_ident)) => item_enum
+			. variants
+			. iter ()
+			. find (|variant| variant . ident == *member_ident)
+			. map (|variant| SelectedMember::Variant (variant . clone ())),
+		_ => return Err
+		(
+			Error::new_spanned
+			(
+				&item_argument . path,
+				"Members can only be selected from structs, unions, traits and enums"
+			)
+		)
+	};
+
+	selected . ok_or_else
+	(
+		|| match &item_argument . field_selector
+		{
+			Some (field_selector) => Error::new_spanned
+			(
+				&field_selector . member,
+				format!
+				(
+					"`{}` has no field `{}`",
+					item_argument . path . to_token_stream (),
+					field_selector . member . to_token_stream ()
+				)
+			),
+			None => Error::new_spanned
+			(
+				&item_argument . path,
+				format!
+				(
+					"`{}` has no member `{}` of type {}",
+					item_argument . item_path () . to_token_stream (),
+					member_ident . map (ToString::to_string) . unwrap_or_default (),
+					item_argument . type_spec
+				)
+			)
+		}
+	)
+}
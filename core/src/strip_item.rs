@@ -0,0 +1,172 @@
+use syn::{
+	Attribute,
+	Item,
+	TraitItem,
+	ImplItem,
+	ForeignItem,
+	Field,
+	Variant,
+	Token
+};
+use syn::fold::{
+	Fold,
+	fold_item,
+	fold_trait_item,
+	fold_impl_item,
+	fold_foreign_item,
+	fold_field,
+	fold_variant
+};
+
+use crate::fn_signature::{strip_fn_body, strip_impl_fn_body};
+
+#[derive (Clone, Copy, Default)]
+pub struct ItemStripper
+{
+	pub bodies: bool,
+	pub docs: bool,
+	pub attrs: bool
+}
+
+fn item_attrs_mut (item: &mut Item) -> Option <&mut Vec <Attribute>>
+{
+	match item
+	{
+		Item::Const (item) => Some (&mut item . attrs),
+		Item::Enum (item) => Some (&mut item . attrs),
+		Item::ExternCrate (item) => Some (&mut item . attrs),
+		Item::Fn (item) => Some (&mut item . attrs),
+		Item::ForeignMod (item) => Some (&mut item . attrs),
+		Item::Impl (item) => Some (&mut item . attrs),
+		Item::Macro (item) => Some (&mut item . attrs),
+		Item::Mod (item) => Some (&mut item . attrs),
+		Item::Static (item) => Some (&mut item . attrs),
+		Item::Struct (item) => Some (&mut item . attrs),
+		Item::Trait (item) => Some (&mut item . attrs),
+		Item::TraitAlias (item) => Some (&mut item . attrs),
+		Item::Type (item) => Some (&mut item . attrs),
+		Item::Union (item) => Some (&mut item . attrs),
+		Item::Use (item) => Some (&mut item . attrs),
+		_ => None
+	}
+}
+
+fn trait_item_attrs_mut (trait_item: &mut TraitItem) -> Option <&mut Vec <Attribute>>
+{
+	match trait_item
+	{
+		TraitItem::Const (trait_item) => Some (&mut trait_item . attrs),
+		TraitItem::Fn (trait_item) => Some (&mut trait_item . attrs),
+		TraitItem::Type (trait_item) => Some (&mut trait_item . attrs),
+		TraitItem::Macro (trait_item) => Some (&mut trait_item . attrs),
+		_ => None
+	}
+}
+
+fn impl_item_attrs_mut (impl_item: &mut ImplItem) -> Option <&mut Vec <Attribute>>
+{
+	match impl_item
+	{
+		ImplItem::Const (impl_item) => Some (&mut impl_item . attrs),
+		ImplItem::Fn (impl_item) => Some (&mut impl_item . attrs),
+		ImplItem::Type (impl_item) => Some (&mut impl_item . attrs),
+		ImplItem::Macro (impl_item) => Some (&mut impl_item . attrs),
+		_ => None
+	}
+}
+
+fn foreign_item_attrs_mut (foreign_item: &mut ForeignItem)
+-> Option <&mut Vec <Attribute>>
+{
+	match foreign_item
+	{
+		ForeignItem::Fn (foreign_item) => Some (&mut foreign_item . attrs),
+		ForeignItem::Static (foreign_item) => Some (&mut foreign_item . attrs),
+		ForeignItem::Type (foreign_item) => Some (&mut foreign_item . attrs),
+		ForeignItem::Macro (foreign_item) => Some (&mut foreign_item . attrs),
+		_ => None
+	}
+}
+
+impl ItemStripper
+{
+	pub fn is_empty (&self) -> bool
+	{
+		! self . bodies && ! self . docs && ! self . attrs
+	}
+
+	fn strip_attrs (&self, attrs: Option <&mut Vec <Attribute>>)
+	{
+		if let Some (attrs) = attrs
+		{
+			attrs . retain
+			(
+				|attr| ! (self . attrs || self . docs && attr . path () . is_ident ("doc"))
+			);
+		}
+	}
+}
+
+impl Fold for ItemStripper
+{
+	fn fold_item (&mut self, node: Item) -> Item
+	{
+		let mut item = fold_item (self, node);
+		self . strip_attrs (item_attrs_mut (&mut item));
+
+		match item
+		{
+			Item::Fn (item_fn) if self . bodies => strip_fn_body (item_fn),
+			item => item
+		}
+	}
+
+	fn fold_trait_item (&mut self, node: TraitItem) -> TraitItem
+	{
+		let mut trait_item = fold_trait_item (self, node);
+		self . strip_attrs (trait_item_attrs_mut (&mut trait_item));
+
+		if let TraitItem::Fn (trait_fn) = &mut trait_item
+		{
+			if self . bodies && trait_fn . default . take () . is_some ()
+			{
+				trait_fn . semi_token = Some (<Token! [;]>::default ());
+			}
+		}
+
+		trait_item
+	}
+
+	fn fold_impl_item (&mut self, node: ImplItem) -> ImplItem
+	{
+		let mut impl_item = fold_impl_item (self, node);
+		self . strip_attrs (impl_item_attrs_mut (&mut impl_item));
+
+		match impl_item
+		{
+			ImplItem::Fn (impl_fn) if self . bodies => strip_impl_fn_body (impl_fn),
+			impl_item => impl_item
+		}
+	}
+
+	fn fold_foreign_item (&mut self, node: ForeignItem) -> ForeignItem
+	{
+		let mut foreign_item = fold_foreign_item (self, node);
+		self . strip_attrs (foreign_item_attrs_mut (&mut foreign_item));
+		foreign_item
+	}
+
+	fn fold_field (&mut self, node: Field) -> Field
+	{
+		let mut field = fold_field (self, node);
+		self . strip_attrs (Some (&mut field . attrs));
+		field
+	}
+
+	fn fold_variant (&mut self, node: Variant) -> Variant
+	{
+		let mut variant = fold_variant (self, node);
+		self . strip_attrs (Some (&mut variant . attrs));
+		variant
+	}
+}
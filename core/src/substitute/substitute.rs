@@ -8,6 +8,7 @@ use syn::{
 	ItemEnum,
 	ItemTrait,
 	ItemFn,
+	ItemImpl,
 	DeriveInput,
 	parse_quote
 };
@@ -145,6 +146,27 @@ pub fn substitute_arguments_for_fn (mut fn_item: ItemFn, fn_path: &Path)
 	Ok ((substitutions, fn_item))
 }
 
+pub fn substitute_arguments_for_impl
+(
+	mut impl_item: ItemImpl,
+	impl_path: &Path
+)
+-> Result <(Substitutions, ItemImpl)>
+{
+	let mut substitutions = Substitutions::try_from_path_arguments
+	(
+		&impl_item . generics . params,
+		&get_path_arguments (impl_path)? . cloned () . unwrap_or_default ()
+	)?;
+
+	impl_item . generics =
+		remove_parameters_from_generics (impl_item . generics);
+
+	let impl_item = substitutions . fold_item_impl (impl_item);
+
+	Ok ((substitutions, impl_item))
+}
+
 pub fn substitute_arguments_for_derive_input
 (
 	mut derive_input: DeriveInput,
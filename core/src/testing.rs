@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+use proc_macro2::TokenStream;
+use syn::{Ident, Item, Macro, Path, Token, parse2};
+use syn::token::{Paren, Brace, Bracket};
+use syn::parse::{Parse, ParseStream, Parser, Result, Error};
+use syn_derive::Parse;
+use quote::{ToTokens, quote};
+
+use crate::{
+	ItemTypeSpec,
+	MultiItemMacroInput,
+	ProtocolHeader,
+	get_macro_path,
+	item_type,
+	sanitize
+};
+
+struct NextItem
+{
+	item_path: Path,
+	type_spec: ItemTypeSpec,
+	macro_path: Path,
+	remaining_items: TokenStream
+}
+
+impl Parse for NextItem
+{
+	fn parse (input: ParseStream <'_>) -> Result <Self>
+	{
+		let item_path = input . parse ()?;
+		input . parse::<Token! [:]> ()?;
+		let type_spec = input . parse ()?;
+		input . parse::<Token! [=>]> ()?;
+		let macro_path = input . parse ()?;
+
+		if ! input . is_empty ()
+		{
+			input . parse::<Token! [,]> ()?;
+		}
+
+		let remaining_items = input . parse ()?;
+
+		Ok (Self {item_path, type_spec, macro_path, remaining_items})
+	}
+}
+
+#[allow (dead_code)]
+#[derive (Parse)]
+struct ItemMacroCall
+{
+	protocol_header: ProtocolHeader,
+	item_path: Path,
+	colon_token: Token! [:],
+	type_spec: ItemTypeSpec,
+
+	#[syn (parenthesized)]
+	paren_token: Paren,
+	#[syn (in = paren_token)]
+	next_items: TokenStream,
+
+	inner_macro_path: Path,
+
+	#[syn (braced)]
+	brace_token: Brace,
+	#[syn (in = brace_token)]
+	items_tokens: TokenStream,
+
+	#[syn (bracketed)]
+	bracket_token: Bracket,
+	#[syn (in = bracket_token)]
+	additional_tokens: TokenStream
+}
+
+fn parse_macro_call (input: ParseStream <'_>) -> Result <Macro>
+{
+	let call = input . parse ()?;
+	input . parse::<Option <Token! [;]>> ()?;
+
+	Ok (call)
+}
+
+// Stands in for the item macros that `#[macrospace::item]` would generate,
+// so that a whole invocation can be expanded without a dependent crate.
+#[derive (Default)]
+pub struct Registry
+{
+	items: HashMap <String, (Ident, Item)>
+}
+
+impl Registry
+{
+	pub fn new () -> Self
+	{
+		Self::default ()
+	}
+
+	pub fn add (&mut self, path: Path, item: Item) -> Result <&mut Self>
+	{
+		let kind = item_type (&item)?;
+		let macro_path = get_macro_path (&path) . to_token_stream () . to_string ();
+
+		self . items . insert (macro_path, (kind, item));
+
+		Ok (self)
+	}
+
+	fn expand_item_macro (&self, call: Macro) -> Result <TokenStream>
+	{
+		let (kind, item) = self
+			. items
+			. get (&call . path . to_token_stream () . to_string ())
+			. ok_or_else
+			(
+				|| Error::new_spanned
+				(
+					&call . path,
+					format! ("Cannot find item macro `{}`", call . path . to_token_stream ())
+				)
+			)?;
+
+		let ItemMacroCall
+		{
+			protocol_header,
+			item_path,
+			type_spec,
+			next_items,
+			inner_macro_path,
+			items_tokens,
+			additional_tokens,
+			..
+		}
+			= parse2 (call . tokens)?;
+
+		// Like the generated item macros, a request for the item's own kind
+		// needs no check, and any other request goes no further than a failed
+		// check, so a mismatch is the only error.
+		if *kind != type_spec . to_token_stream () . to_string ()
+		{
+			type_spec
+				. check (kind, Some (item), false)
+				. map_err (|message| Error::new_spanned (&item_path, message))?;
+		}
+
+		let sanitized_item = sanitize (item);
+		let items_tokens = quote! (#items_tokens (#item_path: #kind, crate) #sanitized_item);
+
+		if next_items . is_empty ()
+		{
+			return Ok (quote! (#inner_macro_path! ({#items_tokens} [#additional_tokens]);));
+		}
+
+		let NextItem {item_path, type_spec, macro_path, remaining_items} =
+			parse2 (next_items)?;
+
+		Ok
+		(
+			quote!
+			{
+				#macro_path!
+				(
+					#protocol_header
+					#item_path: #type_spec
+					(#remaining_items)
+					#inner_macro_path
+					{#items_tokens}
+					[#additional_tokens]
+				);
+			}
+		)
+	}
+
+	// Follows the output of `generate_macrospace_invokation` through every
+	// registered item and returns what the inner macro would be given.
+	pub fn expand <T> (&self, invokation: TokenStream) -> Result <MultiItemMacroInput <T>>
+	where T: Parse
+	{
+		let mut call = parse_macro_call . parse2 (invokation)?;
+
+		while call . tokens . clone () . into_iter () . next () . is_some_and
+		(
+			|token| token . to_string () == "@"
+		)
+		{
+			call = parse_macro_call . parse2 (self . expand_item_macro (call)?)?;
+		}
+
+		parse2 (call . tokens)
+	}
+}
@@ -0,0 +1,86 @@
+use proc_macro2::TokenStream;
+use syn::parse_quote;
+use quote::quote;
+
+use macrospace_core::{
+	ItemArgument,
+	invoke_attribute_consumer,
+	invoke_derive_consumer,
+	generate_macrospace_invokation
+};
+
+fn expected (item_arguments: Vec <ItemArgument>, args: TokenStream, item: TokenStream)
+-> String
+{
+	generate_macrospace_invokation
+	(
+		parse_quote! (::consumer::inner),
+		item_arguments,
+		quote! ({#args} #item)
+	)
+		. to_string ()
+}
+
+fn attribute (attr: TokenStream) -> String
+{
+	invoke_attribute_consumer ("::consumer::inner", attr, quote! (struct A;)) . to_string ()
+}
+
+#[test]
+fn items_and_args ()
+{
+	assert_eq!
+	(
+		attribute (quote! (a::B: struct, c::D: enum; "text", 1)),
+		expected
+		(
+			vec! [parse_quote! (a::B: struct), parse_quote! (c::D: enum)],
+			quote! ("text", 1),
+			quote! (struct A;)
+		)
+	);
+}
+
+#[test]
+fn items_only ()
+{
+	assert_eq!
+	(
+		attribute (quote! (a::B: struct,)),
+		expected (vec! [parse_quote! (a::B: struct)], quote! (), quote! (struct A;))
+	);
+}
+
+#[test]
+fn args_only_with_or_without_semicolon ()
+{
+	for attr in [quote! (; "text"), quote! ("text")]
+	{
+		assert_eq! (attribute (attr), expected (vec! [], quote! ("text"), quote! (struct A;)));
+	}
+
+	assert_eq!
+	(
+		attribute (quote! (name = "text", flag)),
+		expected (vec! [], quote! (name = "text", flag), quote! (struct A;))
+	);
+}
+
+#[test]
+fn malformed_items_before_semicolon_are_reported ()
+{
+	assert! (attribute (quote! (a::B: struct c::D: enum; "text")) . contains ("compile_error"));
+	assert! (attribute (quote! (a::B; "text")) . contains ("compile_error"));
+}
+
+#[test]
+fn derive_takes_args_from_its_helper_attribute ()
+{
+	let item = quote! (#[describe (a::B: struct; "text")] struct A;);
+
+	assert_eq!
+	(
+		invoke_derive_consumer ("::consumer::inner", "describe", item . clone ()) . to_string (),
+		expected (vec! [parse_quote! (a::B: struct)], quote! ("text"), item)
+	);
+}
@@ -0,0 +1,100 @@
+use proc_macro2::TokenStream;
+use syn::{Item, parse_quote};
+use quote::{ToTokens, quote};
+
+use macrospace_core::{
+	ItemArgument,
+	MultiItemMacroInput,
+	SelectedMember,
+	generate_macrospace_invokation
+};
+use macrospace_core::testing::Registry;
+
+fn expand <I> (registry: &Registry, item_arguments: I)
+-> syn::Result <MultiItemMacroInput <TokenStream>>
+where I: IntoIterator <Item = ItemArgument>
+{
+	registry . expand
+	(
+		generate_macrospace_invokation (parse_quote! (consumer::inner), item_arguments, quote! ())
+	)
+}
+
+#[test]
+fn sentinel_named_path_is_fetched ()
+{
+	let item: Item = parse_quote! (pub struct __sanitized_dollar_token_alone__ {});
+
+	let mut registry = Registry::new ();
+	registry
+		. add (parse_quote! (defs::__sanitized_dollar_token_alone__), item . clone ())
+		. unwrap ();
+
+	let input = expand
+	(
+		&registry,
+		[parse_quote! (defs::__sanitized_dollar_token_alone__: struct)]
+	)
+		. unwrap ();
+
+	assert_eq! (input . items_tokens . to_string (), item . to_token_stream () . to_string ());
+	assert_eq!
+	(
+		input . items [0] . path,
+		parse_quote! (defs::__sanitized_dollar_token_alone__)
+	);
+}
+
+#[test]
+fn selected_members_have_their_own_kind ()
+{
+	let mut registry = Registry::new ();
+	registry
+		. add (parse_quote! (defs::Shape), parse_quote! (pub enum Shape { Circle (f32), Square }))
+		. unwrap ()
+		. add (parse_quote! (defs::Point), parse_quote! (pub struct Point { pub x: f32 }))
+		. unwrap ()
+		. add (parse_quote! (defs::Draw), parse_quote! (pub trait Draw { fn draw (&self); }))
+		. unwrap ();
+
+	let input = expand
+	(
+		&registry,
+		[
+			parse_quote! (defs::Shape::Circle: variant),
+			parse_quote! (defs::Point.x: field),
+			parse_quote! (defs::Draw::draw: trait_fn)
+		]
+	)
+		. unwrap ();
+
+	let kinds: Vec <String> = input
+		. items
+		. iter ()
+		. map (|item| item . kind . to_string ())
+		. collect ();
+
+	assert_eq! (kinds, ["variant", "field", "trait_fn"]);
+	assert_eq! (input . items [0] . path, parse_quote! (defs::Shape::Circle));
+
+	assert!
+	(
+		matches!
+		(
+			&input . items [0] . member,
+			Some (SelectedMember::Variant (variant)) if variant . ident == "Circle"
+		)
+	);
+	assert! (matches! (&input . items [1] . member, Some (SelectedMember::Field (_))));
+	assert!
+	(
+		matches!
+		(
+			&input . items [2] . member,
+			Some (SelectedMember::TraitFn (trait_fn)) if trait_fn . sig . ident == "draw"
+		)
+	);
+
+	// The item that the member was selected from is kept alongside it.
+	assert! (matches! (input . items [2] . item, Item::Trait (_)));
+}
@@ -0,0 +1,78 @@
+use proc_macro2::TokenStream;
+use syn::{Item, parse_quote};
+use syn::fold::Fold;
+use quote::{ToTokens, quote};
+
+use macrospace_core::{
+	ItemArgument,
+	ItemStripper,
+	MultiItemMacroInput,
+	generate_macrospace_invokation
+};
+use macrospace_core::testing::Registry;
+
+fn stripped_registry () -> Registry
+{
+	let mut stripper = ItemStripper {bodies: true, ..ItemStripper::default ()};
+	let item: Item = parse_quote! (pub fn identity <T> (x: T) -> T { x });
+
+	let mut registry = Registry::new ();
+	registry
+		. add (parse_quote! (defs::identity), stripper . fold_item (item))
+		. unwrap ();
+
+	registry
+}
+
+fn expand (registry: &Registry, item_argument: ItemArgument)
+-> syn::Result <MultiItemMacroInput <TokenStream>>
+{
+	registry . expand
+	(
+		generate_macrospace_invokation
+		(
+			parse_quote! (consumer::inner),
+			[item_argument],
+			quote! ()
+		)
+	)
+}
+
+#[test]
+fn stripped_fn_keeps_its_kind ()
+{
+	let input = expand (&stripped_registry (), parse_quote! (defs::identity: fn)) . unwrap ();
+
+	assert_eq! (input . items [0] . kind, "fn");
+	assert! (input . items [0] . fn_signature () . is_some ());
+}
+
+#[test]
+fn stripped_fn_reports_generic_arity ()
+{
+	let registry = stripped_registry ();
+
+	assert! (expand (&registry, parse_quote! (defs::identity: fn <1>)) . is_ok ());
+
+	let error = expand (&registry, parse_quote! (defs::identity: fn <2>))
+		. err ()
+		. unwrap ()
+		. to_string ();
+
+	assert! (error . contains ("expected 2 generic parameters, found 1"), "{}", error);
+}
+
+#[test]
+fn stripped_fn_can_be_instantiated ()
+{
+	let input = expand (&stripped_registry (), parse_quote! (defs::identity <u8>: fn!))
+		. unwrap ();
+
+	let expected: Item = parse_quote! (pub fn identity (x: u8) -> u8;);
+
+	assert_eq!
+	(
+		input . items_tokens . to_string (),
+		expected . to_token_stream () . to_string ()
+	);
+}
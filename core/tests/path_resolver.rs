@@ -0,0 +1,116 @@
+use syn::{Item, ItemFn, ItemUse, Path, parse_quote};
+use syn::fold::Fold;
+use quote::{ToTokens, quote};
+
+use macrospace_core::{ItemStripper, strip_fn_body};
+use macrospace_core::path_utils::PathResolver;
+
+fn resolver () -> PathResolver
+{
+	PathResolver::new (parse_quote! (::defs))
+}
+
+fn assert_resolves_to (resolver: &PathResolver, path: Path, expected: Path)
+{
+	assert_eq!
+	(
+		resolver . try_resolve_path (path) . unwrap () . to_token_stream () . to_string (),
+		expected . to_token_stream () . to_string ()
+	);
+}
+
+#[test]
+fn relative_paths_need_a_module ()
+{
+	assert! (resolver () . try_resolve_path (parse_quote! (super::Foo)) . is_err ());
+	assert! (resolver () . try_resolve_path (parse_quote! (self::Foo)) . is_err ());
+
+	let resolver = resolver () . with_module_path ([parse_quote! (a), parse_quote! (b)]);
+
+	assert_resolves_to (&resolver, parse_quote! (super::Foo), parse_quote! (::defs::a::Foo));
+	assert_resolves_to (&resolver, parse_quote! (self::Foo), parse_quote! (::defs::a::b::Foo));
+	assert! (resolver . try_resolve_path (parse_quote! (super::super::super::Foo)) . is_err ());
+}
+
+#[test]
+fn imports_are_resolved ()
+{
+	let mut resolver = resolver ();
+	let item_use: ItemUse = parse_quote! (use {std::collections::HashMap, crate::config::Id as Ident};);
+	resolver . add_use (&item_use);
+
+	assert_resolves_to
+	(
+		&resolver,
+		parse_quote! (HashMap <u8, u8>),
+		parse_quote! (std::collections::HashMap <u8, u8>)
+	);
+
+	let item: Item = parse_quote! (struct Table (HashMap <u8, Ident>););
+	let expected: Item = parse_quote! (struct Table (std::collections::HashMap <u8, ::defs::config::Id>););
+
+	assert_eq!
+	(
+		resolver . try_fold_item (item) . unwrap () . to_token_stream () . to_string (),
+		expected . to_token_stream () . to_string ()
+	);
+}
+
+#[test]
+fn all_unresolved_paths_are_reported ()
+{
+	let item: Item = parse_quote! (struct Pair (super::A, super::B););
+	let error = resolver () . try_fold_item (item) . err () . unwrap ();
+
+	assert_eq! (error . into_iter () . count (), 2);
+}
+
+#[test]
+fn relative_paths_in_bodies_are_left_alone ()
+{
+	let item: Item = parse_quote! (fn f (x: crate::T) -> u8 { let y: super::U = x . into (); y . 0 });
+	let expected: Item = parse_quote! (fn f (x: ::defs::T) -> u8 { let y: super::U = x . into (); y . 0 });
+
+	assert_eq!
+	(
+		resolver () . try_fold_item (item) . unwrap () . to_token_stream () . to_string (),
+		expected . to_token_stream () . to_string ()
+	);
+
+	let item: Item = parse_quote! (const N: usize = self::M + 1;);
+	assert! (resolver () . try_fold_item (item) . is_ok ());
+
+	let item: Item = parse_quote! (fn f (x: super::T) {});
+	assert! (resolver () . try_fold_item (item) . is_err ());
+}
+
+#[test]
+fn stripped_signatures_are_resolved ()
+{
+	let item_fn: ItemFn = parse_quote! (pub fn f (x: crate::T) -> Self { super::g (x) });
+	let item = strip_fn_body (item_fn);
+
+	assert_eq!
+	(
+		resolver () . try_fold_item (item) . unwrap () . to_token_stream () . to_string (),
+		quote! (pub fn f (x: ::defs::T) -> Self;) . to_string ()
+	);
+
+	let item: Item = parse_quote!
+	(
+		impl A
+		{
+			pub fn f (x: crate::T) -> Self { super::g (x) }
+		}
+	);
+	let item = ItemStripper {bodies: true, ..Default::default ()} . fold_item (item);
+
+	assert_eq!
+	(
+		resolver () . try_fold_item (item) . unwrap () . to_token_stream () . to_string (),
+		quote! (impl A { pub fn f (x: ::defs::T) -> Self; }) . to_string ()
+	);
+
+	let item_fn: ItemFn = parse_quote! (fn f (x: super::T) {});
+	assert! (resolver () . try_fold_item (strip_fn_body (item_fn)) . is_err ());
+}
@@ -0,0 +1,187 @@
+use proc_macro2::TokenStream;
+use syn::{Ident, Item, parse_quote};
+use quote::{ToTokens, quote};
+
+use macrospace_core::{
+	ItemArgument,
+	MultiItemMacroInput,
+	generate_macrospace_invokation
+};
+use macrospace_core::testing::Registry;
+
+fn expand <I> (registry: &Registry, item_arguments: I, user_data: TokenStream)
+-> syn::Result <MultiItemMacroInput <TokenStream>>
+where I: IntoIterator <Item = ItemArgument>
+{
+	registry . expand
+	(
+		generate_macrospace_invokation
+		(
+			parse_quote! (consumer::inner),
+			item_arguments,
+			user_data
+		)
+	)
+}
+
+fn registry () -> Registry
+{
+	let mut registry = Registry::new ();
+
+	registry
+		. add (parse_quote! (defs::Point), parse_quote! (pub struct Point { pub x: f32 }))
+		. unwrap ()
+		. add (parse_quote! (defs::Shape), parse_quote! (pub enum Shape { Circle, Square }))
+		. unwrap ()
+		. add
+		(
+			parse_quote! (defs::points),
+			parse_quote!
+			{
+				macro_rules! points
+				{
+					($($x: expr),* $(,)?) => { [$(Point {x: $x}),*] };
+				}
+			}
+		)
+		. unwrap ();
+
+	registry
+}
+
+#[test]
+fn single_item ()
+{
+	let input = expand (&registry (), [parse_quote! (defs::Point: struct)], quote! ()) . unwrap ();
+
+	let expected: Item = parse_quote! (pub struct Point { pub x: f32 });
+
+	assert_eq! (input . items . len (), 1);
+	assert_eq! (input . items [0] . path, parse_quote! (defs::Point));
+	assert_eq! (input . items [0] . kind, "struct");
+	assert_eq! (input . items [0] . item, expected);
+	assert_eq! (input . items_tokens . to_string (), expected . to_token_stream () . to_string ());
+}
+
+#[test]
+fn multiple_items_keep_their_order ()
+{
+	let input = expand
+	(
+		&registry (),
+		[
+			parse_quote! (defs::Shape: enum),
+			parse_quote! (defs::Point: struct),
+			parse_quote! (defs::points: macro)
+		],
+		quote! ()
+	)
+		. unwrap ();
+
+	let paths: Vec <String> = input
+		. items
+		. iter ()
+		. map (|item| item . path . to_token_stream () . to_string ())
+		. collect ();
+
+	assert_eq! (paths, ["defs :: Shape", "defs :: Point", "defs :: points"]);
+	assert_eq! (input . items [2] . kind, "macro");
+}
+
+#[test]
+fn sanitized_payloads_are_restored ()
+{
+	let input = expand
+	(
+		&registry (),
+		[parse_quote! (defs::points: macro)],
+		quote! ($ __sanitized_dollar_token_alone__)
+	)
+		. unwrap ();
+
+	let expected: Item = parse_quote!
+	{
+		macro_rules! points
+		{
+			($($x: expr),* $(,)?) => { [$(Point {x: $x}),*] };
+		}
+	};
+
+	assert_eq! (input . items [0] . item, expected);
+	assert_eq!
+	(
+		input . user_data . to_string (),
+		quote! ($ __sanitized_dollar_token_alone__) . to_string ()
+	);
+}
+
+#[test]
+fn kind_mismatch_is_an_error ()
+{
+	let error = expand (&registry (), [parse_quote! (defs::Point: enum)], quote! ())
+		. err ()
+		. unwrap ()
+		. to_string ();
+
+	assert_eq! (error, "Expected item of type enum, found struct");
+}
+
+#[test]
+fn kind_mismatch_later_in_the_request_is_an_error ()
+{
+	let error = expand
+	(
+		&registry (),
+		[parse_quote! (defs::Point: struct), parse_quote! (defs::Shape: struct | fn)],
+		quote! ()
+	)
+		. err ()
+		. unwrap ()
+		. to_string ();
+
+	assert_eq! (error, "Expected item of type fn, struct, found enum");
+}
+
+#[test]
+fn kind_mismatch_stops_the_request ()
+{
+	let error = expand
+	(
+		&registry (),
+		[parse_quote! (defs::Shape: struct), parse_quote! (defs::Missing: struct)],
+		quote! ()
+	)
+		. err ()
+		. unwrap ();
+
+	assert_eq! (error . clone () . into_iter () . count (), 1);
+	assert_eq! (error . to_string (), "Expected item of type struct, found enum");
+}
+
+#[test]
+fn unknown_item_is_an_error ()
+{
+	let error = expand (&registry (), [parse_quote! (defs::Missing: struct)], quote! ())
+		. err ()
+		. unwrap ()
+		. to_string ();
+
+	assert! (error . starts_with ("Cannot find item macro"), "{}", error);
+}
+
+#[test]
+fn user_data_is_parsed ()
+{
+	let registry = registry ();
+
+	let invokation = generate_macrospace_invokation
+	(
+		parse_quote! (consumer::inner),
+		[parse_quote! (defs::Shape: enum)],
+		quote! (hello)
+	);
+
+	let input: MultiItemMacroInput <Ident> = registry . expand (invokation) . unwrap ();
+
+	assert_eq! (input . user_data, "hello");
+}
@@ -1,56 +1,68 @@
-use syn::{Ident, Path, Token, parse};
+use syn::{Ident, Item, Path, Token, parse, parse2};
 use syn::token::Brace;
 use syn::parse::{Result, Error};
 use syn::ext::IdentExt;
 use syn_derive::Parse;
 
-use macrospace_core::ItemTypeSpec;
+use macrospace_core::{ItemTypeSpec, ProtocolHeader, desanitize};
+
+mod kw
+{
+	syn::custom_keyword! (signature);
+}
 
 #[allow (dead_code)]
 #[derive (Parse)]
 struct CheckItemTypeInput
 {
+	protocol_header: ProtocolHeader,
 	item_path: Path,
 	colon_token: Token! [:],
 	#[parse (Ident::parse_any)]
 	item_type: Ident,
+	signature_token: Option <kw::signature>,
 
 	eq_token: Token! [==],
 
 	expected_item_type_spec: ItemTypeSpec,
 
 	#[syn (braced)]
-	brace_token: Brace,
-	#[syn (in = brace_token)]
-	success_tokens: proc_macro2::TokenStream
+	item_brace_token: Brace,
+	#[syn (in = item_brace_token)]
+	item_tokens: proc_macro2::TokenStream,
+
+	// Only expanded when the check passes.
+	#[syn (braced)]
+	continuation_brace_token: Brace,
+	#[syn (in = continuation_brace_token)]
+	continuation: proc_macro2::TokenStream
 }
 
 fn check_item_type
 (
 	item_path: Path,
 	item_type: Ident,
+	signature_only: bool,
 	expected_item_type_spec: ItemTypeSpec,
-	success_tokens: proc_macro2::TokenStream
+	item_tokens: proc_macro2::TokenStream,
+	continuation: proc_macro2::TokenStream
 )
--> proc_macro2::TokenStream
+-> Result <proc_macro2::TokenStream>
 {
-	if expected_item_type_spec . contains (&item_type)
-	{
-		success_tokens
-	}
-	else
+	let item: Option <Item> =
+		if expected_item_type_spec . has_constraints ()
+		{
+			Some (parse2 (desanitize (item_tokens))?)
+		}
+		else { None };
+
+	match expected_item_type_spec . check (&item_type, item . as_ref (), signature_only)
 	{
-		Error::new_spanned
+		Ok (()) => Ok (continuation),
+		Err (message) => Ok
 		(
-			item_path,
-			format!
-			(
-				"Expected item of type {}, found {}",
-				expected_item_type_spec,
-				item_type
-			)
+			Error::new_spanned (item_path, message) . into_compile_error ()
 		)
-			. into_compile_error ()
 	}
 }
 
@@ -61,8 +73,10 @@ fn try_check_item_type_impl (input: proc_macro::TokenStream)
 	{
 		item_path,
 		item_type,
+		signature_token,
 		expected_item_type_spec,
-		success_tokens,
+		item_tokens,
+		continuation,
 		..
 	}
 		= parse (input)?;
@@ -71,9 +85,11 @@ fn try_check_item_type_impl (input: proc_macro::TokenStream)
 	(
 		item_path,
 		item_type,
+		signature_token . is_some (),
 		expected_item_type_spec,
-		success_tokens
-	);
+		item_tokens,
+		continuation
+	)?;
 
 	Ok (tokens)
 }
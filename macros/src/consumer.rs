@@ -0,0 +1,401 @@
+use syn::{Ident, Path, Type, Visibility, ItemFn, FnArg, Token, parse2, parse_quote};
+use syn::punctuated::Punctuated;
+use syn::parse::{Parse, ParseStream, Result, Error};
+use syn_derive::Parse;
+use quote::{ToTokens, quote, format_ident};
+
+#[allow (dead_code)]
+#[derive (Parse)]
+enum ConsumerArg
+{
+	#[parse (peek = Token! [crate])]
+	Crate (Token! [crate], Token! [=], Path),
+
+	#[parse (peek = kw::consumer_crate)]
+	ConsumerCrate (kw::consumer_crate, Token! [=], Path),
+
+	#[parse (peek = kw::derive)]
+	Derive (kw::derive, Token! [=], Ident)
+}
+
+mod kw
+{
+	syn::custom_keyword! (consumer_crate);
+	syn::custom_keyword! (derive);
+}
+
+#[derive (Default)]
+struct ConsumerArgs
+{
+	macrospace_path: Option <Path>,
+	consumer_crate_path: Option <Path>,
+	derive: Option <Ident>
+}
+
+impl Parse for ConsumerArgs
+{
+	fn parse (input: ParseStream <'_>) -> Result <Self>
+	{
+		let mut consumer_args = Self::default ();
+
+		for consumer_arg in
+			Punctuated::<ConsumerArg, Token! [,]>::parse_terminated (input)?
+		{
+			match consumer_arg
+			{
+				ConsumerArg::Crate (key, _, macrospace_path) =>
+				{
+					if consumer_args . macrospace_path . replace (macrospace_path) . is_some ()
+					{
+						return Err (Error::new_spanned (key, "Duplicate `crate` argument"));
+					}
+				},
+				ConsumerArg::ConsumerCrate (key, _, consumer_crate_path) =>
+				{
+					if consumer_args
+						. consumer_crate_path
+						. replace (consumer_crate_path)
+						. is_some ()
+					{
+						return Err
+						(
+							Error::new_spanned (key, "Duplicate `consumer_crate` argument")
+						);
+					}
+				},
+				ConsumerArg::Derive (key, _, derive) =>
+				{
+					if consumer_args . derive . replace (derive) . is_some ()
+					{
+						return Err (Error::new_spanned (key, "Duplicate `derive` argument"));
+					}
+				}
+			}
+		}
+
+		Ok (consumer_args)
+	}
+}
+
+fn consumer_input_types (consumer_fn: &ItemFn) -> Result <(&Type, &Type, &Type)>
+{
+	let input_types: Vec <&Type> = consumer_fn
+		. sig
+		. inputs
+		. iter ()
+		. filter_map
+		(
+			|input| match input
+			{
+				FnArg::Typed (pat_type) => Some (&*pat_type . ty),
+				FnArg::Receiver (_) => None
+			}
+		)
+		. collect ();
+
+	match input_types . as_slice ()
+	{
+		[annotated_type, fetched_type, args_type]
+			if consumer_fn . sig . inputs . len () == 3 =>
+				Ok ((annotated_type, fetched_type, args_type)),
+		_ => Err
+		(
+			Error::new_spanned
+			(
+				&consumer_fn . sig . inputs,
+				"Consumer functions take `(annotated, fetched, args)`"
+			)
+		)
+	}
+}
+
+// The consumer function is written as `pub fn f (annotated, fetched, args)`.
+// It must be `pub`, since it becomes the proc macro that users invoke, while
+// the function body itself is moved into the hidden inner proc macro.
+// `crate = ...` is the path to macrospace, as for `#[item]`, and
+// `consumer_crate = ...` the path to the crate defining the consumer.
+fn try_consumer_impl
+(
+	attr: proc_macro2::TokenStream,
+	item: proc_macro2::TokenStream
+)
+-> Result <proc_macro2::TokenStream>
+{
+	let ConsumerArgs {macrospace_path, consumer_crate_path, derive} = parse2 (attr)?;
+
+	let macrospace_path = macrospace_path . unwrap_or_else (|| parse_quote! (macrospace));
+
+	let mut consumer_fn: ItemFn = parse2 (item)?;
+	let (annotated_type, fetched_type, args_type) = consumer_input_types (&consumer_fn)?;
+	let (annotated_type, fetched_type, args_type) =
+		(annotated_type . clone (), fetched_type . clone (), args_type . clone ());
+
+	let fn_ident = consumer_fn . sig . ident . clone ();
+	let fn_vis = consumer_fn . vis . clone ();
+	let fn_attrs = std::mem::take (&mut consumer_fn . attrs);
+	consumer_fn . vis = Visibility::Inherited;
+
+	let inner_ident = format_ident! ("__macrospace_consumer_{}", fn_ident);
+	let inner_ident_string = inner_ident . to_string ();
+
+	let inner_macro_path = match consumer_crate_path
+	{
+		Some (consumer_crate_path) =>
+		{
+			let consumer_crate_path_string =
+				consumer_crate_path . to_token_stream () . to_string ();
+			quote! (concat! (#consumer_crate_path_string, "::", #inner_ident_string))
+		},
+		None => quote!
+		(
+			concat! ("::", env! ("CARGO_CRATE_NAME"), "::", #inner_ident_string)
+		)
+	};
+
+	let fetched_types = match &fetched_type
+	{
+		Type::Tuple (type_tuple) => type_tuple . elems . to_token_stream (),
+		_ => fetched_type . to_token_stream ()
+	};
+
+	let outer_macro = match derive
+	{
+		Some (derive) => quote!
+		{
+			#(#fn_attrs)*
+			#[proc_macro_derive (#derive, attributes (#fn_ident))]
+			#fn_vis fn #fn_ident (item: proc_macro::TokenStream)
+			-> proc_macro::TokenStream
+			{
+				#macrospace_path::invoke_derive_consumer
+				(
+					#inner_macro_path,
+					stringify! (#fn_ident),
+					item . into ()
+				)
+					. into ()
+			}
+		},
+		None => quote!
+		{
+			#(#fn_attrs)*
+			#[proc_macro_attribute]
+			#fn_vis fn #fn_ident
+			(
+				attr: proc_macro::TokenStream,
+				item: proc_macro::TokenStream
+			)
+			-> proc_macro::TokenStream
+			{
+				#macrospace_path::invoke_attribute_consumer
+				(
+					#inner_macro_path,
+					attr . into (),
+					item . into ()
+				)
+					. into ()
+			}
+		}
+	};
+
+	Ok
+	(
+		quote!
+		{
+			#outer_macro
+
+			#[doc (hidden)]
+			#[proc_macro]
+			pub fn #inner_ident (input: proc_macro::TokenStream)
+			-> proc_macro::TokenStream
+			{
+				#consumer_fn
+
+				let result = #macrospace_path::parse_args!
+				(
+					crate = #macrospace_path,
+					input => (#fetched_types),
+					#macrospace_path::ConsumerData <#args_type, #annotated_type>
+				)
+					. and_then
+					(
+						|(fetched, #macrospace_path::ConsumerData {args, annotated})|
+						#fn_ident (annotated, fetched, args)
+					);
+
+				match result
+				{
+					Ok (tokens) => tokens . into (),
+					Err (error) => error . into_compile_error () . into ()
+				}
+			}
+		}
+	)
+}
+
+pub fn consumer_impl
+(
+	attr: proc_macro::TokenStream,
+	item: proc_macro::TokenStream
+)
+-> proc_macro::TokenStream
+{
+	try_consumer_impl (attr . into (), item . into ())
+		. unwrap_or_else (Error::into_compile_error)
+		. into ()
+}
+
+#[cfg (test)]
+mod tests
+{
+	use proc_macro2::TokenStream;
+	use syn::{File, Item};
+	use quote::quote;
+
+	use super::try_consumer_impl;
+
+	fn expand (attr: TokenStream) -> (String, Vec <Item>)
+	{
+		let expanded = try_consumer_impl
+		(
+			attr,
+			quote!
+			{
+				/// Describes things.
+				pub fn describe
+				(
+					annotated: syn::ItemStruct,
+					fetched: (syn::ItemEnum, syn::ItemFn),
+					args: syn::LitStr
+				)
+				-> syn::Result <proc_macro2::TokenStream>
+				{
+					Ok (quote::ToTokens::into_token_stream (annotated))
+				}
+			}
+		)
+			. unwrap ();
+
+		let file: File = syn::parse2 (expanded . clone ()) . unwrap ();
+
+		(expanded . to_string (), file . items)
+	}
+
+	fn assert_contains (expanded: &str, tokens: TokenStream)
+	{
+		assert! (expanded . contains (&tokens . to_string ()), "{}\nin\n{}", tokens, expanded);
+	}
+
+	fn fn_attrs (item: &Item) -> Vec <String>
+	{
+		let Item::Fn (item_fn) = item
+		else
+		{
+			panic! ("Expected a fn, found {}", quote! (#item));
+		};
+
+		item_fn . attrs . iter () . map (|attr| quote! (#attr) . to_string ()) . collect ()
+	}
+
+	#[test]
+	fn attribute_form ()
+	{
+		let (expanded, items) = expand (quote! ());
+
+		assert_eq! (items . len (), 2);
+		assert_eq!
+		(
+			fn_attrs (&items [0]),
+			[
+				quote! (#[doc = r" Describes things."]) . to_string (),
+				quote! (#[proc_macro_attribute]) . to_string ()
+			]
+		);
+		assert_eq!
+		(
+			fn_attrs (&items [1]),
+			[quote! (#[doc (hidden)]) . to_string (), quote! (#[proc_macro]) . to_string ()]
+		);
+
+		assert_contains (&expanded, quote! (pub fn describe (attr: proc_macro::TokenStream, item: proc_macro::TokenStream)));
+		assert_contains (&expanded, quote! (macrospace::invoke_attribute_consumer));
+		assert_contains
+		(
+			&expanded,
+			quote! (concat! ("::", env! ("CARGO_CRATE_NAME"), "::", "__macrospace_consumer_describe"))
+		);
+		assert_contains
+		(
+			&expanded,
+			quote!
+			(
+				macrospace::parse_args!
+				(
+					crate = macrospace,
+					input => (syn::ItemEnum, syn::ItemFn),
+					macrospace::ConsumerData <syn::LitStr, syn::ItemStruct>
+				)
+			)
+		);
+		assert_contains (&expanded, quote! (describe (annotated, fetched, args)));
+	}
+
+	#[test]
+	fn derive_form ()
+	{
+		let (expanded, items) = expand (quote! (derive = Describe));
+
+		assert_eq! (items . len (), 2);
+		assert_eq!
+		(
+			fn_attrs (&items [0]),
+			[
+				quote! (#[doc = r" Describes things."]) . to_string (),
+				quote! (#[proc_macro_derive (Describe, attributes (describe))]) . to_string ()
+			]
+		);
+
+		assert_contains (&expanded, quote! (pub fn describe (item: proc_macro::TokenStream)));
+		assert_contains (&expanded, quote! (macrospace::invoke_derive_consumer));
+		assert_contains (&expanded, quote! (stringify! (describe)));
+	}
+
+	#[test]
+	fn crate_paths ()
+	{
+		let (expanded, _) = expand
+		(
+			quote! (crate = ::facade::macrospace, consumer_crate = ::my_consumer)
+		);
+
+		assert_contains (&expanded, quote! (::facade::macrospace::invoke_attribute_consumer));
+		assert_contains (&expanded, quote! (concat! (":: my_consumer", "::", "__macrospace_consumer_describe")));
+		assert_contains
+		(
+			&expanded,
+			quote!
+			(
+				::facade::macrospace::parse_args!
+				(
+					crate = ::facade::macrospace,
+					input => (syn::ItemEnum, syn::ItemFn),
+					::facade::macrospace::ConsumerData <syn::LitStr, syn::ItemStruct>
+				)
+			)
+		);
+		assert! (! expanded . replace (":: facade :: macrospace", "") . contains ("macrospace ::"));
+	}
+
+	#[test]
+	fn wrong_arity_is_rejected ()
+	{
+		let error = try_consumer_impl
+		(
+			quote! (),
+			quote! (pub fn describe (annotated: syn::Item) -> syn::Result <proc_macro2::TokenStream> {}),
+		)
+			. unwrap_err ();
+
+		assert_eq! (error . to_string (), "Consumer functions take `(annotated, fetched, args)`");
+	}
+}
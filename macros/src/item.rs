@@ -1,38 +1,108 @@
-use syn::{Ident, Visibility, Item, parse};
-use syn::parse::{Nothing, Result, Error};
+use syn::{
+	Ident,
+	Path,
+	Visibility,
+	Generics,
+	Item,
+	ItemMacro,
+	ItemMod,
+	Token,
+	parse,
+	parse_quote
+};
+use syn::parse::{Result, Error};
 use syn::spanned::Spanned;
+use syn::fold::Fold;
 use quote::ToTokens;
 
-use macrospace_core::generate_item_macro;
+use macrospace_core::{
+	ItemStripper,
+	item_type,
+	is_macro_rules,
+	generate_item_macro,
+	defining_crate_placeholder,
+	reject_defining_crate_placeholder
+};
+use macrospace_core::path_utils::{PathResolver, join_paths};
 
-fn item_visibility (item: &Item) -> Result <&Visibility>
+use crate::item_args::{ItemArgs, ExportScope};
+
+fn is_macro_exported (item: &ItemMacro) -> bool
+{
+	item . attrs . iter () . any (|attr| attr . path () . is_ident ("macro_export"))
+}
+
+fn item_visibility (item: &Item) -> Result <Visibility>
 {
 	match item
 	{
-		Item::Const (item) => Ok (&item . vis),
-		Item::Enum (item) => Ok (&item . vis),
-		Item::ExternCrate (item) => Ok (&item . vis),
-		Item::Fn (item) => Ok (&item . vis),
-		Item::Mod (item) => Ok (&item . vis),
-		Item::Static (item) => Ok (&item . vis),
-		Item::Struct (item) => Ok (&item . vis),
-		Item::Trait (item) => Ok (&item . vis),
-		Item::TraitAlias (item) => Ok (&item . vis),
-		Item::Type (item) => Ok (&item . vis),
-		Item::Union (item) => Ok (&item . vis),
-		Item::Use (item) => Ok (&item . vis),
+		Item::Const (item) => Ok (item . vis . clone ()),
+		Item::Enum (item) => Ok (item . vis . clone ()),
+		Item::ExternCrate (item) => Ok (item . vis . clone ()),
+		Item::Fn (item) => Ok (item . vis . clone ()),
+		// Blocks have no visibility of their own, so they stay crate-local
+		// unless exported with `export = pub`.
+		Item::ForeignMod (_) | Item::Impl (_) => Ok (Visibility::Inherited),
+		Item::Macro (item) if is_macro_rules (item) =>
+			if is_macro_exported (item)
+		{
+			Ok (Visibility::Public (<Token! [pub]>::default ()))
+		}
+		else
+		{
+			Ok (Visibility::Inherited)
+		},
+		Item::Mod (item) => Ok (item . vis . clone ()),
+		Item::Static (item) => Ok (item . vis . clone ()),
+		Item::Struct (item) => Ok (item . vis . clone ()),
+		Item::Trait (item) => Ok (item . vis . clone ()),
+		Item::TraitAlias (item) => Ok (item . vis . clone ()),
+		Item::Type (item) => Ok (item . vis . clone ()),
+		Item::Union (item) => Ok (item . vis . clone ()),
+		Item::Use (item) => Ok (item . vis . clone ()),
 		_ => Err (Error::new_spanned (item, "Unsupported item"))
 	}
 }
 
-fn item_ident (item: &Item) -> Result <&Ident>
+fn item_ident <'a> (item: &'a Item, name: Option <&'a Ident>) -> Result <&'a Ident>
 {
+	match (item, name)
+	{
+		(Item::ForeignMod (_) | Item::Impl (_), Some (name)) => return Ok (name),
+		(Item::ForeignMod (item), None) => return Err
+		(
+			Error::new
+			(
+				item . abi . extern_token . span (),
+				"Extern blocks must be named with `#[item (name = ...)]`"
+			)
+		),
+		(Item::Impl (item), None) => return Err
+		(
+			Error::new
+			(
+				item . impl_token . span (),
+				"Impl blocks must be named with `#[item (name = ...)]`"
+			)
+		),
+		(_, Some (name)) => return Err
+		(
+			Error::new_spanned
+			(
+				name,
+				"Only unnamed items such as impl and extern blocks may be given a `name`"
+			)
+		),
+		_ => {}
+	}
+
 	match item
 	{
 		Item::Const (item) => Ok (&item . ident),
 		Item::Enum (item) => Ok (&item . ident),
 		Item::ExternCrate (item) => Ok (&item . ident),
 		Item::Fn (item) => Ok (&item . sig . ident),
+		Item::Macro (ItemMacro {ident: Some (ident), ..}) => Ok (ident),
 		Item::Mod (item) => Ok (&item . ident),
 		Item::Static (item) => Ok (&item . ident),
 		Item::Struct (item) => Ok (&item . ident),
@@ -44,53 +114,334 @@ fn item_ident (item: &Item) -> Result <&Ident>
 	}
 }
 
-fn item_type (item: &Item) -> Result <Ident>
+fn export_visibility (item: &Item, export: Option <ExportScope>) -> Result <Visibility>
 {
-	match item
+	let item_visibility = item_visibility (item)?;
+
+	match (export, &item_visibility)
 	{
-		Item::Const (item) =>
-			Ok (Ident::new ("const", item . const_token . span ())),
-		Item::Enum (item) =>
-			Ok (Ident::new ("enum", item . enum_token . span ())),
-		Item::ExternCrate (item) =>
-			Ok (Ident::new ("extern", item . extern_token . span ())),
-		Item::Fn (item) =>
-			Ok (Ident::new ("fn", item . sig . fn_token . span ())),
-		Item::Mod (item) =>
-			Ok (Ident::new ("mod", item . mod_token . span ())),
-		Item::Static (item) =>
-			Ok (Ident::new ("static", item . static_token . span ())),
-		Item::Struct (item) =>
-			Ok (Ident::new ("struct", item . struct_token . span ())),
-		Item::Trait (item) =>
-			Ok (Ident::new ("trait", item . trait_token . span ())),
-		Item::TraitAlias (item) =>
-			Ok (Ident::new ("alias", item . trait_token . span ())),
-		Item::Type (item) =>
-			Ok (Ident::new ("type", item . type_token . span ())),
-		Item::Union (item) =>
-			Ok (Ident::new ("union", item . union_token . span ())),
-		Item::Use (item) =>
-			Ok (Ident::new ("use", item . use_token . span ())),
-		_ => Err (Error::new_spanned (item, "Unsupported item"))
+		(Some (ExportScope::Crate (crate_token)), Visibility::Public (_)) =>
+			Ok (parse_quote! (pub (#crate_token))),
+		(Some (ExportScope::Pub (pub_token)), _) => Ok (Visibility::Public (pub_token)),
+		_ => Ok (item_visibility)
+	}
+}
+
+fn is_exported_by_hand (item: &Item) -> bool
+{
+	let attrs = match item
+	{
+		Item::Const (item) => &item . attrs,
+		Item::Enum (item) => &item . attrs,
+		Item::ExternCrate (item) => &item . attrs,
+		Item::Fn (item) => &item . attrs,
+		Item::ForeignMod (item) => &item . attrs,
+		Item::Impl (item) => &item . attrs,
+		Item::Macro (item) => &item . attrs,
+		Item::Mod (item) => &item . attrs,
+		Item::Static (item) => &item . attrs,
+		Item::Struct (item) => &item . attrs,
+		Item::Trait (item) => &item . attrs,
+		Item::TraitAlias (item) => &item . attrs,
+		Item::Type (item) => &item . attrs,
+		Item::Union (item) => &item . attrs,
+		Item::Use (item) => &item . attrs,
+		_ => return false
+	};
+
+	attrs . iter () . any
+	(
+		|attr| attr
+			. path ()
+			. segments
+			. last ()
+			. is_some_and (|segment| segment . ident == "item")
+	)
+}
+
+// `Self` is only rewritten when it can be fully qualified, since a bare
+// name would refer to whatever is in scope where the item is expanded.
+fn self_path (resolver: &PathResolver, ident: &Ident, generics: &Generics) -> Option <Path>
+{
+	let (_, type_generics, _) = generics . split_for_impl ();
+
+	let self_path: Path = parse_quote! (#ident #type_generics);
+
+	resolver
+		. module_prefix ()
+		. map (|module_prefix| join_paths (&module_prefix, self_path . segments))
+}
+
+fn resolve_item (resolver: &PathResolver, item: &Item) -> Result <Item>
+{
+	// Module contents are resolved against the module they are declared in.
+	if let Item::Mod (item_mod @ ItemMod {content: Some ((brace_token, content)), ..}) = item
+	{
+		let child_resolver = child_resolver (resolver, item_mod);
+
+		let content = content
+			. iter ()
+			. map (|child| resolve_item (&child_resolver, child))
+			. collect::<Result <_>> ()?;
+
+		return Ok
+		(
+			Item::Mod
+			(
+				ItemMod
+				{
+					content: Some ((*brace_token, content)),
+					..item_mod . clone ()
+				}
+			)
+		);
 	}
+
+	let self_path = match item
+	{
+		Item::Enum (item) => self_path (resolver, &item . ident, &item . generics),
+		Item::Struct (item) => self_path (resolver, &item . ident, &item . generics),
+		Item::Union (item) => self_path (resolver, &item . ident, &item . generics),
+		_ => None
+	};
+
+	let mut resolver = resolver . clone ();
+
+	if let Some (self_path) = self_path
+	{
+		resolver = resolver . with_self_path (self_path);
+	}
+
+	resolver . try_fold_item (item . clone ())
+}
+
+// Stripping comes first, so that nothing is resolved in a body that is not
+// exported.
+fn item_payload (resolver: &PathResolver, mut stripper: ItemStripper, item: &Item)
+-> Result <Item>
+{
+	if stripper . is_empty ()
+	{
+		return resolve_item (resolver, item);
+	}
+
+	resolve_item (resolver, &stripper . fold_item (item . clone ()))
+}
+
+fn child_resolver (resolver: &PathResolver, item_mod: &ItemMod) -> PathResolver
+{
+	let mut child_resolver = PathResolver::new (resolver . crate_path . clone ());
+
+	if let Some (module_path) = &resolver . module_path
+	{
+		child_resolver = child_resolver . with_module_path
+		(
+			module_path . iter () . chain ([&item_mod . ident]) . cloned ()
+		);
+	}
+
+	for item in item_mod . content . iter () . flat_map (|(_, content)| content)
+	{
+		if let Item::Use (item_use) = item
+		{
+			child_resolver . add_use (item_use);
+		}
+	}
+
+	child_resolver
+}
+
+fn export_children
+(
+	item_mod: &mut ItemMod,
+	export: Option <ExportScope>,
+	macrospace_path: &Path,
+	resolver: &PathResolver,
+	stripper: ItemStripper,
+	scope: &[Ident]
+)
+-> Result <()>
+{
+	let resolver = child_resolver (resolver, item_mod);
+	let scope: Vec <Ident> = scope . iter () . chain ([&item_mod . ident]) . cloned () . collect ();
+
+	let Some ((_, content)) = &mut item_mod . content
+	else
+	{
+		return Err
+		(
+			Error::new_spanned
+			(
+				&item_mod . ident,
+				"Recursive export requires an inline module"
+			)
+		);
+	};
+
+	let mut child_macros = Vec::new ();
+
+	for child in content . iter_mut ()
+	{
+		if is_exported_by_hand (child)
+		{
+			continue;
+		}
+
+		// Imports and macro invocations are not items, blocks have no name to
+		// fetch them by unless given one by hand, and children that are not
+		// public would only leave an unused import of their item macro.
+		let is_exported = match &*child
+		{
+			Item::Use (_) | Item::Impl (_) | Item::ForeignMod (_) => false,
+			Item::Macro (item_macro) if ! is_macro_rules (item_macro) => false,
+			child => matches! (item_visibility (child)?, Visibility::Public (_))
+		};
+
+		if is_exported
+		{
+			child_macros . push
+			(
+				Item::Verbatim
+				(
+					generate_item_macro
+					(
+						item_ident (child, None)?,
+						&item_type (child)?,
+						&export_visibility (child, export)?,
+						macrospace_path,
+						stripper . bodies,
+						resolver . module_path . as_deref (),
+						&scope,
+						&item_payload (&resolver, stripper, child)?
+					)
+				)
+			);
+		}
+
+		if let Item::Mod (child_mod) = child
+		{
+			if child_mod . content . is_some ()
+			{
+				export_children
+				(
+					child_mod,
+					export,
+					macrospace_path,
+					&resolver,
+					stripper,
+					&scope
+				)?;
+			}
+		}
+	}
+
+	content . extend (child_macros);
+
+	Ok (())
 }
 
 fn try_item_impl (attr: proc_macro::TokenStream, item: proc_macro::TokenStream)
 -> Result <proc_macro2::TokenStream>
 {
-	let _: Nothing = parse (attr)?;
+	let item_args: ItemArgs = parse (attr)?;
 
 	let mut tokens = proc_macro2::TokenStream::from (item . clone ());
 
+	reject_defining_crate_placeholder (&tokens)?;
+
 	let item = parse (item)?;
 
+	let item_ident = item_ident (&item, item_args . name . as_ref ())?;
+
+	let macrospace_path = item_args
+		. macrospace_path
+		. unwrap_or_else (|| parse_quote! (macrospace));
+
+	let defining_crate = defining_crate_placeholder ();
+	let mut resolver = PathResolver::new (parse_quote! (#defining_crate));
+
+	if let Some (module) = item_args . module
+	{
+		resolver = resolver . with_module_path
+		(
+			module
+				. segments
+				. into_iter ()
+				. map (|segment| segment . ident)
+				. skip_while (|ident| ident == "crate")
+		);
+	}
+
+	// A single item cannot see the imports of the module around it, so they
+	// have to be given with `use = ...`.
+	if let Some (imports) = &item_args . imports
+	{
+		if item_args . recursive . is_some ()
+		{
+			return Err
+			(
+				Error::new_spanned
+				(
+					imports,
+					"Recursive export takes its imports from the module itself"
+				)
+			);
+		}
+
+		resolver . add_use (&parse_quote! (use #imports;));
+	}
+
+	let stripper = item_args
+		. signature
+		. map
+		(
+			|signature_options| ItemStripper
+			{
+				bodies: true,
+				docs: signature_options . docs,
+				attrs: signature_options . attrs
+			}
+		)
+		. unwrap_or_default ();
+
+	if let Some (recursive_token) = item_args . recursive
+	{
+		let Item::Mod (mut item_mod) = item . clone ()
+		else
+		{
+			return Err
+			(
+				Error::new_spanned
+				(
+					recursive_token,
+					"Recursive export is only supported on modules"
+				)
+			);
+		};
+
+		export_children
+		(
+			&mut item_mod,
+			item_args . export,
+			&macrospace_path,
+			&resolver,
+			stripper,
+			&[]
+		)?;
+
+		tokens = item_mod . into_token_stream ();
+	}
+
 	generate_item_macro
 	(
-		item_ident (&item)?,
+		item_args . alias . as_ref () . unwrap_or (item_ident),
 		&item_type (&item)?,
-		item_visibility (&item)?,
-		&item
+		&export_visibility (&item, item_args . export)?,
+		&macrospace_path,
+		stripper . bodies,
+		resolver . module_path . as_deref (),
+		&[],
+		&item_payload (&resolver, stripper, &item)?
 	)
 		. to_tokens (&mut tokens);
 
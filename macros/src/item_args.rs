@@ -0,0 +1,174 @@
+use syn::{Ident, Path, UseTree, Token, parenthesized};
+use syn::token::Paren;
+use syn::punctuated::Punctuated;
+use syn::parse::{Parse, ParseStream, Result, Error};
+use syn_derive::Parse;
+use quote::ToTokens;
+
+pub mod kw
+{
+	syn::custom_keyword! (name);
+	syn::custom_keyword! (export);
+	syn::custom_keyword! (recursive);
+	syn::custom_keyword! (module);
+	syn::custom_keyword! (signature);
+}
+
+#[allow (dead_code)]
+#[derive (Parse)]
+enum ItemArg
+{
+	#[parse (peek = kw::name)]
+	Name (kw::name, Token! [=], Ident),
+
+	#[parse (peek = Token! [as])]
+	As (Token! [as], Token! [=], Ident),
+
+	#[parse (peek = Token! [crate])]
+	Crate (Token! [crate], Token! [=], Path),
+
+	#[parse (peek = Token! [use])]
+	Use (Token! [use], Token! [=], UseTree),
+
+	#[parse (peek = kw::export)]
+	Export (kw::export, Token! [=], ExportScope),
+
+	#[parse (peek = kw::recursive)]
+	Recursive (kw::recursive),
+
+	#[parse (peek = kw::module)]
+	Module (kw::module, Token! [=], Path),
+
+	#[parse (peek = kw::signature)]
+	Signature (kw::signature, SignatureOptions)
+}
+
+// `export = crate` keeps a `pub` item's macro crate-local, and `export = pub`
+// exports the macro of an item that is crate-local by default.
+#[derive (Clone, Copy, Parse)]
+pub enum ExportScope
+{
+	#[parse (peek = Token! [crate])]
+	Crate (Token! [crate]),
+
+	#[parse (peek = Token! [pub])]
+	Pub (Token! [pub])
+}
+
+#[derive (Default)]
+pub struct SignatureOptions
+{
+	pub docs: bool,
+	pub attrs: bool
+}
+
+impl Parse for SignatureOptions
+{
+	fn parse (input: ParseStream <'_>) -> Result <Self>
+	{
+		let mut signature_options = Self::default ();
+
+		if ! input . peek (Paren)
+		{
+			return Ok (signature_options);
+		}
+
+		let content;
+		parenthesized! (content in input);
+
+		for option in Punctuated::<Ident, Token! [,]>::parse_terminated (&content)?
+		{
+			if option == "docs"
+			{
+				signature_options . docs = true;
+			}
+			else if option == "attrs"
+			{
+				signature_options . attrs = true;
+			}
+			else
+			{
+				return Err
+				(
+					Error::new_spanned
+					(
+						&option,
+						format!
+						(
+							"Unknown signature option `{}`, expected `docs` or `attrs`",
+							option
+						)
+					)
+				);
+			}
+		}
+
+		Ok (signature_options)
+	}
+}
+
+#[derive (Default)]
+pub struct ItemArgs
+{
+	pub name: Option <Ident>,
+	pub alias: Option <Ident>,
+	pub macrospace_path: Option <Path>,
+	pub imports: Option <UseTree>,
+	pub export: Option <ExportScope>,
+	pub recursive: Option <kw::recursive>,
+	pub module: Option <Path>,
+	pub signature: Option <SignatureOptions>
+}
+
+fn set_once <T, K> (slot: &mut Option <T>, key: K, value: T) -> Result <()>
+where K: ToTokens
+{
+	if slot . is_some ()
+	{
+		return Err
+		(
+			Error::new_spanned
+			(
+				&key,
+				format! ("Duplicate `{}` argument", key . to_token_stream ())
+			)
+		);
+	}
+
+	*slot = Some (value);
+
+	Ok (())
+}
+
+impl Parse for ItemArgs
+{
+	fn parse (input: ParseStream <'_>) -> Result <Self>
+	{
+		let mut item_args = Self::default ();
+
+		for item_arg in Punctuated::<ItemArg, Token! [,]>::parse_terminated (input)?
+		{
+			match item_arg
+			{
+				ItemArg::Name (key, _, name) =>
+					set_once (&mut item_args . name, key, name)?,
+				ItemArg::As (key, _, alias) =>
+					set_once (&mut item_args . alias, key, alias)?,
+				ItemArg::Crate (key, _, path) =>
+					set_once (&mut item_args . macrospace_path, key, path)?,
+				ItemArg::Use (key, _, imports) =>
+					set_once (&mut item_args . imports, key, imports)?,
+				ItemArg::Export (key, _, scope) =>
+					set_once (&mut item_args . export, key, scope)?,
+				ItemArg::Recursive (key) =>
+					set_once (&mut item_args . recursive, key, key)?,
+				ItemArg::Module (key, _, module) =>
+					set_once (&mut item_args . module, key, module)?,
+				ItemArg::Signature (key, signature_options) =>
+					set_once (&mut item_args . signature, key, signature_options)?
+			}
+		}
+
+		Ok (item_args)
+	}
+}
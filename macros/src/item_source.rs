@@ -0,0 +1,123 @@
+use syn::{Ident, Token, parse};
+use syn::punctuated::Punctuated;
+use syn::parse::{Result, Error};
+use syn_derive::Parse;
+use quote::{ToTokens, quote};
+use proc_macro2::Span;
+
+use macrospace_core::{ItemArgument, MultiItemMacroInput, generate_macrospace_invokation};
+
+use crate::macrospace_path::MacrospacePath;
+
+#[derive (Parse)]
+struct ItemSourceInput
+{
+	macrospace_path: MacrospacePath,
+
+	#[parse (Punctuated::parse_terminated)]
+	required_items: Punctuated <ItemArgument, Token! [,]>
+}
+
+fn try_item_source_impl (input: proc_macro::TokenStream)
+-> Result <proc_macro2::TokenStream>
+{
+	let ItemSourceInput {macrospace_path, required_items} = parse (input)?;
+
+	// The callback defines this const inside the block, so the name travels
+	// through the invocation as user data to keep the same hygiene context.
+	let source_ident = Ident::new ("__MACROSPACE_ITEM_SOURCE", Span::call_site ());
+
+	let invokation = generate_macrospace_invokation
+	(
+		macrospace_path . callback ("item_source_callback"),
+		required_items,
+		&source_ident
+	);
+
+	Ok (quote! ({ #invokation #source_ident }))
+}
+
+pub fn item_source_impl (input: proc_macro::TokenStream)
+-> proc_macro::TokenStream
+{
+	try_item_source_impl (input)
+		. unwrap_or_else (Error::into_compile_error)
+		. into ()
+}
+
+fn try_item_source_callback_impl (input: proc_macro::TokenStream)
+-> Result <proc_macro2::TokenStream>
+{
+	let MultiItemMacroInput {items_tokens, user_data, ..}: MultiItemMacroInput <Ident>
+		= parse (input)?;
+
+	let source = items_tokens . to_string ();
+
+	Ok (quote! (const #user_data: &'static str = #source;))
+}
+
+pub fn item_source_callback_impl (input: proc_macro::TokenStream)
+-> proc_macro::TokenStream
+{
+	try_item_source_callback_impl (input)
+		. unwrap_or_else (Error::into_compile_error)
+		. into ()
+}
+
+fn try_item_dump_impl (input: proc_macro::TokenStream)
+-> Result <proc_macro2::TokenStream>
+{
+	let ItemSourceInput {macrospace_path, required_items} = parse (input)?;
+
+	// Carries the call site span through the invocation for the error.
+	let dump_ident = Ident::new ("item_dump", Span::call_site ());
+
+	Ok
+	(
+		generate_macrospace_invokation
+		(
+			macrospace_path . callback ("item_dump_callback"),
+			required_items,
+			dump_ident
+		)
+	)
+}
+
+pub fn item_dump_impl (input: proc_macro::TokenStream)
+-> proc_macro::TokenStream
+{
+	try_item_dump_impl (input)
+		. unwrap_or_else (Error::into_compile_error)
+		. into ()
+}
+
+fn try_item_dump_callback_impl (input: proc_macro::TokenStream)
+-> Result <proc_macro2::TokenStream>
+{
+	let MultiItemMacroInput {items_tokens, items, user_data, ..}: MultiItemMacroInput <Ident>
+		= parse (input)?;
+
+	let mut message = String::new ();
+
+	for item in &items
+	{
+		message += &format!
+		(
+			"`{}`: {}\n",
+			item . path . to_token_stream (),
+			item . kind
+		);
+	}
+
+	message += &format! ("\n{}", items_tokens);
+
+	Ok (Error::new (user_data . span (), message) . into_compile_error ())
+}
+
+pub fn item_dump_callback_impl (input: proc_macro::TokenStream)
+-> proc_macro::TokenStream
+{
+	try_item_dump_callback_impl (input)
+		. unwrap_or_else (Error::into_compile_error)
+		. into ()
+}
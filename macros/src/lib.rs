@@ -1,13 +1,17 @@
 use proc_macro::TokenStream;
 
 mod transform_use;
+mod item_args;
+mod macrospace_path;
 
 mod check_item_type;
-mod invoke_item_macro;
+mod consumer;
 mod item;
+mod item_source;
 mod import;
 mod invoke;
 mod parse_args;
+mod with_items;
 
 #[doc (hidden)]
 #[proc_macro]
@@ -16,11 +20,10 @@ pub fn check_item_type (input: TokenStream) -> TokenStream
 	check_item_type::check_item_type_impl (input)
 }
 
-#[doc (hidden)]
-#[proc_macro]
-pub fn invoke_item_macro (input: TokenStream) -> TokenStream
+#[proc_macro_attribute]
+pub fn consumer (attr: TokenStream, item: TokenStream) -> TokenStream
 {
-	invoke_item_macro::invoke_item_macro_impl (input)
+	consumer::consumer_impl (attr, item)
 }
 
 #[proc_macro_attribute]
@@ -29,6 +32,32 @@ pub fn item (attr: TokenStream, item: TokenStream) -> TokenStream
 	item::item_impl (attr, item)
 }
 
+#[proc_macro]
+pub fn item_source (input: TokenStream) -> TokenStream
+{
+	item_source::item_source_impl (input)
+}
+
+#[doc (hidden)]
+#[proc_macro]
+pub fn item_source_callback (input: TokenStream) -> TokenStream
+{
+	item_source::item_source_callback_impl (input)
+}
+
+#[proc_macro]
+pub fn item_dump (input: TokenStream) -> TokenStream
+{
+	item_source::item_dump_impl (input)
+}
+
+#[doc (hidden)]
+#[proc_macro]
+pub fn item_dump_callback (input: TokenStream) -> TokenStream
+{
+	item_source::item_dump_callback_impl (input)
+}
+
 #[proc_macro_attribute]
 pub fn import (attr: TokenStream, item: TokenStream) -> TokenStream
 {
@@ -52,3 +81,16 @@ pub fn parse_args (input: TokenStream) -> TokenStream
 {
 	parse_args::parse_args_impl (input)
 }
+
+#[proc_macro]
+pub fn with_items (input: TokenStream) -> TokenStream
+{
+	with_items::with_items_impl (input)
+}
+
+#[doc (hidden)]
+#[proc_macro]
+pub fn with_items_callback (input: TokenStream) -> TokenStream
+{
+	with_items::with_items_callback_impl (input)
+}
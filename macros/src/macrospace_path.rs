@@ -0,0 +1,37 @@
+use proc_macro2::Span;
+use syn::{Ident, Path, Token, parse_quote};
+use syn::parse::{Parse, ParseStream, Result};
+
+// An optional leading `crate = path,` for crates that only reach macrospace
+// through a re-export, like `#[item (crate = path)]`.
+pub struct MacrospacePath (pub Path);
+
+impl MacrospacePath
+{
+	pub fn callback (&self, callback_ident: &str) -> Path
+	{
+		let macrospace_path = &self . 0;
+		let callback_ident = Ident::new (callback_ident, Span::call_site ());
+
+		parse_quote! (#macrospace_path::#callback_ident)
+	}
+}
+
+impl Parse for MacrospacePath
+{
+	fn parse (input: ParseStream <'_>) -> Result <Self>
+	{
+		// `crate::...` is an item path, so only `crate =` is taken here.
+		if ! (input . peek (Token! [crate]) && input . peek2 (Token! [=]))
+		{
+			return Ok (Self (parse_quote! (macrospace)));
+		}
+
+		input . parse::<Token! [crate]> ()?;
+		input . parse::<Token! [=]> ()?;
+		let macrospace_path = input . parse ()?;
+		input . parse::<Token! [,]> ()?;
+
+		Ok (Self (macrospace_path))
+	}
+}
@@ -1,9 +1,23 @@
-use syn::{Ident, Index, Expr, Token, parse};
-use syn::parse::{Result, Error};
+use syn::{
+	Ident,
+	Index,
+	Expr,
+	Type,
+	PathArguments,
+	GenericArgument,
+	Path,
+	Token,
+	parse
+};
+use syn::token::Paren;
+use syn::punctuated::Punctuated;
+use syn::parse::{ParseStream, Result, Error};
 use syn_derive::Parse;
-use quote::{quote, format_ident};
+use quote::{ToTokens, quote, format_ident};
 
-fn parse_args (n: Index, input: Expr) -> proc_macro2::TokenStream
+use crate::macrospace_path::MacrospacePath;
+
+fn parse_args (macrospace_path: &Path, n: Index, input: Expr) -> proc_macro2::TokenStream
 {
 	let item_var_names: Vec <Ident> =
 		(0..(n . index)) . map (|i| format_ident! ("x{}", i)) . collect ();
@@ -15,7 +29,7 @@ fn parse_args (n: Index, input: Expr) -> proc_macro2::TokenStream
 			{
 				use syn::parse::Parser;
 
-				let macrospace::MultiItemMacroInput {items_tokens, user_data, ..}
+				let #macrospace_path::MultiItemMacroInput {items_tokens, user_data, ..}
 					= syn::parse (input)?;
 
 				let parse_items = |input: syn::parse::ParseStream <'_>|
@@ -32,21 +46,245 @@ fn parse_args (n: Index, input: Expr) -> proc_macro2::TokenStream
 	}
 }
 
-#[allow (dead_code)]
+fn vec_element_type (ty: &Type) -> Option <&Type>
+{
+	let Type::Path (type_path) = ty
+	else
+	{
+		return None;
+	};
+
+	let last_segment = type_path . path . segments . last ()?;
+
+	match &last_segment . arguments
+	{
+		PathArguments::AngleBracketed (arguments)
+			if last_segment . ident == "Vec" && arguments . args . len () == 1 =>
+				match arguments . args . first ()
+			{
+				Some (GenericArgument::Type (element_type)) => Some (element_type),
+				_ => None
+			},
+		_ => None
+	}
+}
+
+fn parse_typed_args
+(
+	macrospace_path: &Path,
+	input: Expr,
+	item_types: Punctuated <Type, Token! [,]>,
+	user_data_type: Option <Type>
+)
+-> proc_macro2::TokenStream
+{
+	let num_item_types = item_types . len ();
+	let mut item_var_names = Vec::new ();
+	let mut parse_statements = Vec::new ();
+
+	for (i, item_type) in item_types . iter () . enumerate ()
+	{
+		let item_var_name = format_ident! ("x{}", i);
+
+		let parse_statement = match vec_element_type (item_type)
+		{
+			Some (element_type) if i + 1 == num_item_types =>
+			{
+				let element_type_string =
+					element_type . to_token_stream () . to_string ();
+
+				quote!
+				{
+					let mut #item_var_name: #item_type = Vec::new ();
+
+					while ! input . is_empty ()
+					{
+						let position = #i + #item_var_name . len ();
+
+						#item_var_name . push
+						(
+							input . parse::<#element_type> () . map_err
+							(
+								|error| syn::Error::new
+								(
+									error . span (),
+									format!
+									(
+										"Failed to parse argument {} as `{}`: {}",
+										position,
+										#element_type_string,
+										error
+									)
+								)
+							)?
+						);
+					}
+				}
+			},
+			_ =>
+			{
+				let item_type_string = item_type . to_token_stream () . to_string ();
+
+				quote!
+				{
+					let #item_var_name = input . parse::<#item_type> () . map_err
+					(
+						|error| syn::Error::new
+						(
+							error . span (),
+							format!
+							(
+								"Failed to parse argument {} as `{}`: {}",
+								#i,
+								#item_type_string,
+								error
+							)
+						)
+					)?;
+				}
+			}
+		};
+
+		item_var_names . push (item_var_name);
+		parse_statements . push (parse_statement);
+	}
+
+	let items =
+		if item_types . trailing_punct () { quote! ((#(#item_var_names,)*)) }
+		else { quote! ((#(#item_var_names),*)) };
+
+	let parse_user_data = match user_data_type
+	{
+		Some (user_data_type) =>
+		{
+			let user_data_type_string =
+				user_data_type . to_token_stream () . to_string ();
+
+			quote!
+			{
+				let user_data = syn::parse2::<#user_data_type> (user_data) . map_err
+				(
+					|error| syn::Error::new
+					(
+						error . span (),
+						format!
+						(
+							"Failed to parse user data as `{}`: {}",
+							#user_data_type_string,
+							error
+						)
+					)
+				)?;
+			}
+		},
+		None => quote! { let user_data = syn::parse2 (user_data)?; }
+	};
+
+	quote!
+	{
+		(
+			|input|
+			{
+				use syn::parse::Parser;
+
+				let #macrospace_path::MultiItemMacroInput {items_tokens, user_data, ..}
+					= syn::parse (input)?;
+
+				let parse_items = |input: syn::parse::ParseStream <'_>|
+				{
+					#(#parse_statements)*
+
+					syn::Result::Ok (#items)
+				};
+
+				let items = parse_items . parse2 (items_tokens)?;
+
+				#parse_user_data
+
+				syn::Result::Ok ((items, user_data))
+			}
+		)
+		(#input)
+	}
+}
+
+fn parse_user_data_type (input: ParseStream <'_>) -> Result <Option <Type>>
+{
+	if input . is_empty ()
+	{
+		return Ok (None);
+	}
+
+	input . parse::<Token! [,]> ()?;
+
+	Ok (Some (input . parse ()?))
+}
+
+#[allow (dead_code, clippy::large_enum_variant)]
 #[derive (Parse)]
-struct ParseArgsInput
+enum ParseArgsInput
 {
-	n: Index,
-	comma_token: Token! [,],
-	input: Expr
+	#[parse (peek_func = |input| input . peek (syn::LitInt) && input . peek2 (Token! [,]))]
+	Count
+	{
+		n: Index,
+		comma_token: Token! [,],
+		input: Expr
+	},
+
+	Typed
+	{
+		input: Expr,
+		fat_arrow_token: Token! [=>],
+
+		#[syn (parenthesized)]
+		paren_token: Paren,
+		#[syn (in = paren_token)]
+		#[parse (Punctuated::parse_terminated)]
+		item_types: Punctuated <Type, Token! [,]>,
+
+		#[parse (parse_user_data_type)]
+		user_data_type: Option <Type>
+	}
+}
+
+#[derive (Parse)]
+struct ParseArgsMacroInput
+{
+	macrospace_path: MacrospacePath,
+	parse_args_input: ParseArgsInput
 }
 
 fn try_parse_args_impl (input: proc_macro::TokenStream)
 -> Result <proc_macro2::TokenStream>
 {
-	let ParseArgsInput {n, input, ..} = parse (input)?;
+	let ParseArgsMacroInput {macrospace_path, parse_args_input} = parse (input)?;
+	let MacrospacePath (macrospace_path) = macrospace_path;
 
-	Ok (parse_args (n, input))
+	match parse_args_input
+	{
+		ParseArgsInput::Count {n, input, ..} => Ok (parse_args (&macrospace_path, n, input)),
+		ParseArgsInput::Typed {input, item_types, user_data_type, ..} =>
+		{
+			if let Some (item_type) = item_types
+				. iter ()
+				. rev ()
+				. skip (1)
+				. find (|item_type| vec_element_type (item_type) . is_some ())
+			{
+				return Err
+				(
+					Error::new_spanned
+					(
+						item_type,
+						"Only the last argument may be a `Vec`"
+					)
+				);
+			}
+
+			Ok (parse_typed_args (&macrospace_path, input, item_types, user_data_type))
+		}
+	}
 }
 
 pub fn parse_args_impl (input: proc_macro::TokenStream)
@@ -0,0 +1,88 @@
+use syn::{Macro, MacroDelimiter, Token, parse};
+use syn::token::Paren;
+use syn::punctuated::Punctuated;
+use syn::parse::{Result, Error};
+use syn_derive::Parse;
+use quote::{ToTokens, quote};
+
+use macrospace_core::{ItemArgument, MultiItemMacroInput, generate_macrospace_invokation};
+
+use crate::macrospace_path::MacrospacePath;
+
+#[allow (dead_code)]
+#[derive (Parse)]
+struct WithItemsInput
+{
+	macrospace_path: MacrospacePath,
+
+	#[syn (parenthesized)]
+	paren_token: Paren,
+	#[syn (in = paren_token)]
+	#[parse (Punctuated::parse_terminated)]
+	required_items: Punctuated <ItemArgument, Token! [,]>,
+
+	fat_arrow_token: Token! [=>],
+
+	callback: Macro
+}
+
+fn try_with_items_impl (input: proc_macro::TokenStream)
+-> Result <proc_macro2::TokenStream>
+{
+	let WithItemsInput {macrospace_path, required_items, callback, ..} = parse (input)?;
+
+	Ok
+	(
+		generate_macrospace_invokation
+		(
+			macrospace_path . callback ("with_items_callback"),
+			required_items,
+			callback
+		)
+	)
+}
+
+pub fn with_items_impl (input: proc_macro::TokenStream)
+-> proc_macro::TokenStream
+{
+	try_with_items_impl (input)
+		. unwrap_or_else (Error::into_compile_error)
+		. into ()
+}
+
+fn try_with_items_callback_impl (input: proc_macro::TokenStream)
+-> Result <proc_macro2::TokenStream>
+{
+	let MultiItemMacroInput {items_tokens, user_data, ..} = parse (input)?;
+
+	let Macro {path, bang_token, delimiter, tokens} = user_data;
+
+	let tokens = quote! ({#items_tokens} #tokens);
+
+	let mut callback = quote! (#path #bang_token);
+
+	match &delimiter
+	{
+		MacroDelimiter::Paren (paren) =>
+			paren . surround (&mut callback, |inner| tokens . to_tokens (inner)),
+		MacroDelimiter::Brace (brace) =>
+			brace . surround (&mut callback, |inner| tokens . to_tokens (inner)),
+		MacroDelimiter::Bracket (bracket) =>
+			bracket . surround (&mut callback, |inner| tokens . to_tokens (inner))
+	}
+
+	if ! matches! (delimiter, MacroDelimiter::Brace (_))
+	{
+		<Token! [;]>::default () . to_tokens (&mut callback);
+	}
+
+	Ok (callback)
+}
+
+pub fn with_items_callback_impl (input: proc_macro::TokenStream)
+-> proc_macro::TokenStream
+{
+	try_with_items_callback_impl (input)
+		. unwrap_or_else (Error::into_compile_error)
+		. into ()
+}
@@ -1,24 +1,39 @@
 pub use macrospace_core
 ::{
 	ItemTypeMismatch,
+	ItemTypeSpec,
+	ItemArgument,
+	FetchedItem,
+	SelectedMember,
 	MultiItemMacroInput,
+	Invocation,
+	ConsumerData,
+	invoke_attribute_consumer,
+	invoke_derive_consumer,
 	generate_item_macro,
-	generate_macrospace_invokation
+	generate_macrospace_invokation,
+	generics,
+	path_utils,
+	substitute,
+	testing
 };
 
 pub use macrospace_macros::{
 	check_item_type,
-	invoke_item_macro,
+	consumer,
 	item,
+	item_source,
+	item_source_callback,
+	item_dump,
+	item_dump_callback,
 	import,
 	import_exclusive,
 	invoke,
-	parse_args
+	parse_args,
+	with_items,
+	with_items_callback
 };
 
-pub mod generics;
-pub mod path_utils;
-pub mod substitute;
 pub mod stream_utils;
 pub mod struct_utils;
 pub mod enum_utils;
@@ -0,0 +1,224 @@
+use itertools::Itertools;
+use proc_macro2::{TokenStream, TokenTree, Delimiter, Punct};
+use syn::{Ident, Token, parenthesized};
+use syn::punctuated::Punctuated;
+use syn::parse::{Parse, ParseStream};
+use quote::ToTokens;
+
+use super::{
+	StructuredBindingView,
+	IndexBindings,
+	VisitationError,
+	SpecializationError,
+	NoParameterInRepetition,
+	PatternBuffer,
+	PatternVisitor,
+	AlternationVisitor,
+	TokenizeBinding
+};
+
+#[derive (Clone, Debug)]
+pub struct AlternationPattern <T>
+{
+	pub dollar_token: syn::token::Dollar,
+	pub paren_token: syn::token::Paren,
+	pub branches: Punctuated <PatternBuffer <T>, syn::token::Or>
+}
+
+impl <T> AlternationPattern <T>
+{
+	// `$( ... )` is an alternation when its contents have a top level `|` and
+	// it is not followed by a repetition operator, optionally preceded by a
+	// separator, so that `$(a | b)?` and `$(| $x: ident |)*` stay repetitions.
+	pub fn peek (input: ParseStream <'_>) -> bool
+	{
+		let fork = input . fork ();
+
+		if fork . parse::<Token! [$]> () . is_err ()
+		{
+			return false;
+		}
+
+		let Ok (TokenTree::Group (group)) = fork . parse::<TokenTree> ()
+		else
+		{
+			return false;
+		};
+
+		group . delimiter () == Delimiter::Parenthesis
+			&& group . stream () . into_iter () . any
+			(
+				|token| matches! (&token, TokenTree::Punct (punct) if punct . as_char () == '|')
+			)
+			&& ! Self::peek_repetition_operator (&fork)
+	}
+
+	// Mirrors how repetitions read their operator, with a separator being any
+	// single punctuation token.
+	fn peek_repetition_operator (input: ParseStream <'_>) -> bool
+	{
+		if input . peek (Token! [?]) || input . peek (Token! [*]) || input . peek (Token! [+])
+		{
+			return true;
+		}
+
+		input . parse::<Punct> () . is_ok ()
+			&& (input . peek (Token! [*]) || input . peek (Token! [+]))
+	}
+
+	pub fn referenced_identifiers (&self) -> impl Iterator <Item = &Ident>
+	{
+		self
+			. branches
+			. iter ()
+			. flat_map (PatternBuffer::referenced_identifiers)
+			. unique ()
+	}
+
+	pub fn assert_has_parameters (&self)
+	-> Result <(), NoParameterInRepetition <T>>
+	where T: Clone
+	{
+		if self . referenced_identifiers () . next () . is_none ()
+		{
+			Err (NoParameterInRepetition::new (self . clone ()))
+		}
+		else
+		{
+			Ok (())
+		}
+	}
+
+	pub fn validate (&self) -> Result <(), NoParameterInRepetition <T>>
+	where T: Clone
+	{
+		for branch in &self . branches
+		{
+			branch . validate ()?;
+		}
+
+		self . assert_has_parameters ()?;
+
+		Ok (())
+	}
+
+	pub fn visit <V> (&self, index_bindings: &IndexBindings, visitor: &mut V)
+	-> Result <(), VisitationError <V::Error>>
+	where V: PatternVisitor <T>
+	{
+		let mut alternation_visitor = visitor . pre_visit_alternation
+		(
+			self . referenced_identifiers ()
+		)
+			. map_err (VisitationError::Visitor)?;
+
+		for (i, branch) in self . branches . iter () . enumerate ()
+		{
+			if let Some (mut branch_visitor) = alternation_visitor
+				. pre_visit_branch (i)
+				. map_err (VisitationError::Visitor)?
+			{
+				let visit_result = branch . visit (index_bindings, &mut branch_visitor);
+
+				if alternation_visitor . post_visit_branch (i, branch_visitor, visit_result)?
+				{
+					break;
+				}
+			}
+		}
+
+		visitor . post_visit_alternation
+		(
+			self . referenced_identifiers (),
+			alternation_visitor
+		)
+			. map_err (VisitationError::Visitor)?;
+
+		Ok (())
+	}
+
+	pub fn specialize <'a, V>
+	(
+		&self,
+		index_bindings: &IndexBindings,
+		bindings: &StructuredBindingView <'a, V>,
+		pattern_buffer: &mut PatternBuffer <T>
+	)
+	-> Result <(), SpecializationError <T::Error>>
+	where T: Clone + Parse + TokenizeBinding <V>
+	{
+		let alternation_view = match bindings . project (self . referenced_identifiers ())
+		{
+			Ok (projected_bindings) => projected_bindings . get_alternation_view ()?,
+			Err (_) => None
+		};
+
+		match alternation_view
+		{
+			Some ((branch, branch_bindings)) => self . branches [branch] . specialize
+			(
+				index_bindings,
+				&branch_bindings,
+				pattern_buffer
+			)?,
+			None => pattern_buffer . append_alternation (self . clone ())
+		}
+
+		Ok (())
+	}
+}
+
+impl <T> Parse for AlternationPattern <T>
+where T: Parse
+{
+	fn parse (input: ParseStream <'_>) -> syn::Result <Self>
+	{
+		let dollar_token = input . parse ()?;
+
+		let content;
+		let paren_token = parenthesized! (content in input);
+
+		let mut branches = Punctuated::new ();
+		let mut branch = PatternBuffer::new ();
+
+		while ! content . is_empty ()
+		{
+			if content . peek (Token! [|])
+			{
+				branches . push_value (branch);
+				branches . push_punct (content . parse ()?);
+				branch = PatternBuffer::new ();
+			}
+			else
+			{
+				branch . append_item (content . parse ()?);
+			}
+		}
+
+		branches . push_value (branch);
+
+		Ok (Self {dollar_token, paren_token, branches})
+	}
+}
+
+impl <T> ToTokens for AlternationPattern <T>
+where T: ToTokens
+{
+	fn to_tokens (&self, tokens: &mut TokenStream)
+	{
+		self . dollar_token . to_tokens (tokens);
+
+		self . paren_token . surround
+		(
+			tokens,
+			|inner_tokens|
+			{
+				for pair in self . branches . pairs ()
+				{
+					pair . value () . to_tokens (inner_tokens);
+					pair . punct () . to_tokens (inner_tokens);
+				}
+			}
+		);
+	}
+}
@@ -0,0 +1,100 @@
+use std::fmt::{Display, Formatter};
+
+use proc_macro2::{Delimiter, Literal, Span};
+use syn::Ident;
+
+use super::Fragment;
+
+// `macro_rules` only lets some fragments be followed by a fixed set of
+// tokens, so that the language can grow without changing how a matcher that
+// compiles today splits its input.
+const EXPR_FOLLOW: &[&str] = &["=>", ",", ";"];
+const PAT_FOLLOW: &[&str] = &["=>", ",", "=", "if", "in"];
+const PATH_FOLLOW: &[&str] = &["=>", ",", "=", "|", ";", ":", ">", ">>", "as", "where"];
+const TYPE_START: &[&str] = &["!", "*", "&", "&&", "?", "<", "::", "'"];
+
+// Something that can come right after a fragment in a matcher.
+#[derive (Clone, Debug)]
+pub enum Follower
+{
+	Ident (Ident),
+	// Joint punctuation is a single token, such as `=>` or `>>`.
+	Punct (String, Span),
+	Literal (Literal),
+	Fragment (Ident, Fragment),
+	Group (Delimiter, Span),
+	End
+}
+
+impl Follower
+{
+	pub fn may_follow (&self, fragment: Fragment) -> bool
+	{
+		match (fragment, self)
+		{
+			(_, Self::End) => true,
+			(Fragment::Expr | Fragment::Stmt, Self::Punct (punct, _)) =>
+				EXPR_FOLLOW . contains (&punct . as_str ()),
+			(Fragment::Pat, Self::Punct (punct, _)) =>
+				PAT_FOLLOW . contains (&punct . as_str ()),
+			(Fragment::Pat, Self::Ident (ident)) =>
+				PAT_FOLLOW . iter () . any (|follow| ident == follow),
+			(Fragment::Path | Fragment::Ty, Self::Punct (punct, _)) =>
+				PATH_FOLLOW . contains (&punct . as_str ()),
+			(Fragment::Path | Fragment::Ty, Self::Ident (ident)) =>
+				PATH_FOLLOW . iter () . any (|follow| ident == follow),
+			(Fragment::Path | Fragment::Ty, Self::Group (delimiter, _)) =>
+				matches! (delimiter, Delimiter::Bracket | Delimiter::Brace),
+			(Fragment::Path | Fragment::Ty, Self::Fragment (_, follower)) =>
+				*follower == Fragment::Block,
+			(Fragment::Vis, Self::Punct (punct, _)) =>
+				punct == "," || TYPE_START . contains (&punct . as_str ()),
+			(Fragment::Vis, Self::Ident (ident)) => ident != "priv",
+			(Fragment::Vis, Self::Group (delimiter, _)) =>
+				matches! (delimiter, Delimiter::Parenthesis | Delimiter::Bracket),
+			(Fragment::Vis, Self::Fragment (_, follower)) =>
+				matches! (follower, Fragment::Ident | Fragment::Ty | Fragment::Path),
+			(
+				Fragment::Expr
+					| Fragment::Stmt
+					| Fragment::Pat
+					| Fragment::Path
+					| Fragment::Ty
+					| Fragment::Vis,
+				_
+			) => false,
+			_ => true
+		}
+	}
+
+	pub fn span (&self) -> Span
+	{
+		match self
+		{
+			Self::Ident (ident) | Self::Fragment (ident, _) => ident . span (),
+			Self::Punct (_, span) | Self::Group (_, span) => *span,
+			Self::Literal (literal) => literal . span (),
+			Self::End => Span::call_site ()
+		}
+	}
+}
+
+impl Display for Follower
+{
+	fn fmt (&self, f: &mut Formatter <'_>) -> Result <(), std::fmt::Error>
+	{
+		match self
+		{
+			Self::Ident (ident) => f . write_fmt (format_args! ("`{}`", ident)),
+			Self::Punct (punct, _) => f . write_fmt (format_args! ("`{}`", punct)),
+			Self::Literal (literal) => f . write_fmt (format_args! ("`{}`", literal)),
+			Self::Fragment (ident, fragment) =>
+				f . write_fmt (format_args! ("`${}: {}`", ident, fragment)),
+			Self::Group (Delimiter::Parenthesis, _) => f . write_str ("`(`"),
+			Self::Group (Delimiter::Bracket, _) => f . write_str ("`[`"),
+			Self::Group (Delimiter::Brace, _) => f . write_str ("`{`"),
+			Self::Group (Delimiter::None, _) => f . write_str ("an undelimited group"),
+			Self::End => f . write_str ("the end of the matcher")
+		}
+	}
+}
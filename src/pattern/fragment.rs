@@ -0,0 +1,366 @@
+use std::fmt::{Display, Formatter};
+
+use proc_macro2::{TokenStream, TokenTree, Span};
+use syn::{
+	Ident,
+	Type,
+	Expr,
+	ExprBlock,
+	Path,
+	Lifetime,
+	Lit,
+	Visibility,
+	Block,
+	Item,
+	Pat,
+	PatType,
+	Meta,
+	Stmt,
+	Local,
+	LocalInit,
+	Attribute,
+	Token
+};
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream, Error};
+use syn::parse::discouraged::Speculative;
+use quote::{ToTokens, TokenStreamExt};
+
+use super::{
+	ParseBinding,
+	TokenizeBinding,
+	MacroRulesBinding,
+	ParameterBindingTypeMismatch
+};
+
+// The fragment specifiers of `macro_rules`, for use as `$x: ty` in a
+// `Pattern <TypeAnnotation <Fragment>>`.
+#[derive (Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Fragment
+{
+	Ident,
+	Ty,
+	Expr,
+	Path,
+	Tt,
+	Lifetime,
+	Literal,
+	Vis,
+	Block,
+	Item,
+	Pat,
+	Meta,
+	Stmt
+}
+
+impl Fragment
+{
+	pub fn as_str (&self) -> &'static str
+	{
+		match self
+		{
+			Self::Ident => "ident",
+			Self::Ty => "ty",
+			Self::Expr => "expr",
+			Self::Path => "path",
+			Self::Tt => "tt",
+			Self::Lifetime => "lifetime",
+			Self::Literal => "literal",
+			Self::Vis => "vis",
+			Self::Block => "block",
+			Self::Item => "item",
+			Self::Pat => "pat",
+			Self::Meta => "meta",
+			Self::Stmt => "stmt"
+		}
+	}
+}
+
+impl Parse for Fragment
+{
+	fn parse (input: ParseStream <'_>) -> syn::Result <Self>
+	{
+		let ident = Ident::parse_any (input)?;
+
+		match ident . to_string () . as_str ()
+		{
+			"ident" => Ok (Self::Ident),
+			"ty" => Ok (Self::Ty),
+			"expr" => Ok (Self::Expr),
+			"path" => Ok (Self::Path),
+			"tt" => Ok (Self::Tt),
+			"lifetime" => Ok (Self::Lifetime),
+			"literal" => Ok (Self::Literal),
+			"vis" => Ok (Self::Vis),
+			"block" => Ok (Self::Block),
+			"item" => Ok (Self::Item),
+			"pat" => Ok (Self::Pat),
+			"meta" => Ok (Self::Meta),
+			"stmt" => Ok (Self::Stmt),
+			_ => Err (Error::new_spanned (ident, "Unknown fragment specifier"))
+		}
+	}
+}
+
+impl ToTokens for Fragment
+{
+	fn to_tokens (&self, tokens: &mut TokenStream)
+	{
+		Ident::new (self . as_str (), Span::call_site ()) . to_tokens (tokens);
+	}
+}
+
+impl Display for Fragment
+{
+	fn fmt (&self, f: &mut Formatter <'_>) -> Result <(), std::fmt::Error>
+	{
+		f . write_str (self . as_str ())
+	}
+}
+
+// `let` statements without their semicolon, which syn always requires.
+fn parse_local (input: ParseStream <'_>, attrs: Vec <Attribute>) -> syn::Result <Local>
+{
+	let let_token = input . parse ()?;
+
+	let mut pat = Pat::parse_single (input)?;
+
+	if input . peek (Token! [:])
+	{
+		pat = Pat::Type
+		(
+			PatType
+			{
+				attrs: Vec::new (),
+				pat: Box::new (pat),
+				colon_token: input . parse ()?,
+				ty: input . parse ()?
+			}
+		);
+	}
+
+	let init = match input . parse::<Option <Token! [=]>> ()?
+	{
+		Some (eq_token) =>
+		{
+			let expr = input . parse ()?;
+
+			let diverge = match input . parse::<Option <Token! [else]>> ()?
+			{
+				Some (else_token) =>
+				{
+					let diverge = ExprBlock
+					{
+						attrs: Vec::new (),
+						label: None,
+						block: input . parse ()?
+					};
+
+					Some ((else_token, Box::new (Expr::Block (diverge))))
+				},
+				None => None
+			};
+
+			Some (LocalInit {eq_token, expr, diverge})
+		},
+		None => None
+	};
+
+	Ok (Local {attrs, let_token, pat, init, semi_token: Default::default ()})
+}
+
+// Like `macro_rules`, a statement stops before its trailing semicolon, unless
+// it is an item, which keeps any semicolon that it requires.
+fn parse_stmt (input: ParseStream <'_>) -> syn::Result <Stmt>
+{
+	let fork = input . fork ();
+	let attrs = fork . call (Attribute::parse_outer)?;
+
+	if fork . peek (Token! [let])
+	{
+		input . advance_to (&fork);
+
+		return parse_local (input, attrs) . map (Stmt::Local);
+	}
+
+	let fork = input . fork ();
+
+	if let Ok (Stmt::Item (item)) = fork . parse ()
+	{
+		input . advance_to (&fork);
+
+		return Ok (Stmt::Item (item));
+	}
+
+	Ok (Stmt::Expr (input . parse ()?, None))
+}
+
+fn local_to_tokens (local: &Local, tokens: &mut TokenStream)
+{
+	tokens . append_all (&local . attrs);
+	local . let_token . to_tokens (tokens);
+	local . pat . to_tokens (tokens);
+
+	if let Some (init) = &local . init
+	{
+		init . eq_token . to_tokens (tokens);
+		init . expr . to_tokens (tokens);
+
+		if let Some ((else_token, diverge)) = &init . diverge
+		{
+			else_token . to_tokens (tokens);
+			diverge . to_tokens (tokens);
+		}
+	}
+}
+
+impl ParseBinding <FragmentValue> for Fragment
+{
+	fn parse (&self, input: ParseStream <'_>) -> syn::Result <FragmentValue>
+	{
+		match self
+		{
+			Self::Ident => Ok (FragmentValue::Ident (Ident::parse_any (input)?)),
+			Self::Ty => Ok (FragmentValue::Ty (input . parse ()?)),
+			Self::Expr => Ok (FragmentValue::Expr (input . parse ()?)),
+			Self::Path => Ok (FragmentValue::Path (input . parse ()?)),
+			Self::Tt => Ok (FragmentValue::Tt (input . parse ()?)),
+			Self::Lifetime => Ok (FragmentValue::Lifetime (input . parse ()?)),
+			Self::Literal => Ok (FragmentValue::Literal (input . parse ()?, input . parse ()?)),
+			Self::Vis => Ok (FragmentValue::Vis (input . parse ()?)),
+			Self::Block => Ok (FragmentValue::Block (input . parse ()?)),
+			Self::Item => Ok (FragmentValue::Item (input . parse ()?)),
+			Self::Pat => Ok (FragmentValue::Pat (Pat::parse_multi_with_leading_vert (input)?)),
+			Self::Meta => Ok (FragmentValue::Meta (input . parse ()?)),
+			Self::Stmt => Ok (FragmentValue::Stmt (parse_stmt (input)?))
+		}
+	}
+}
+
+impl TokenizeBinding <FragmentValue> for Fragment
+{
+	type Error = ParameterBindingTypeMismatch <FragmentValue, Fragment>;
+
+	fn tokenize (&self, ident: &Ident, binding: &FragmentValue, tokens: &mut TokenStream)
+	-> Result <(), Self::Error>
+	{
+		if binding . fragment () != *self
+		{
+			return Err
+			(
+				ParameterBindingTypeMismatch::new
+				(
+					ident . clone (),
+					binding . clone (),
+					binding . fragment (),
+					*self
+				)
+			);
+		}
+
+		binding . to_tokens (tokens);
+
+		Ok (())
+	}
+}
+
+impl MacroRulesBinding for Fragment
+{
+	fn fragment (&self) -> Option <Fragment>
+	{
+		Some (*self)
+	}
+}
+
+#[derive (Clone, Debug)]
+pub enum FragmentValue
+{
+	Ident (Ident),
+	Ty (Type),
+	Expr (Expr),
+	Path (Path),
+	Tt (TokenTree),
+	Lifetime (Lifetime),
+	Literal (Option <Token! [-]>, Lit),
+	Vis (Visibility),
+	Block (Block),
+	Item (Item),
+	Pat (Pat),
+	Meta (Meta),
+	Stmt (Stmt)
+}
+
+impl FragmentValue
+{
+	pub fn fragment (&self) -> Fragment
+	{
+		match self
+		{
+			Self::Ident (_) => Fragment::Ident,
+			Self::Ty (_) => Fragment::Ty,
+			Self::Expr (_) => Fragment::Expr,
+			Self::Path (_) => Fragment::Path,
+			Self::Tt (_) => Fragment::Tt,
+			Self::Lifetime (_) => Fragment::Lifetime,
+			Self::Literal (..) => Fragment::Literal,
+			Self::Vis (_) => Fragment::Vis,
+			Self::Block (_) => Fragment::Block,
+			Self::Item (_) => Fragment::Item,
+			Self::Pat (_) => Fragment::Pat,
+			Self::Meta (_) => Fragment::Meta,
+			Self::Stmt (_) => Fragment::Stmt
+		}
+	}
+}
+
+impl ToTokens for FragmentValue
+{
+	fn to_tokens (&self, tokens: &mut TokenStream)
+	{
+		match self
+		{
+			Self::Ident (ident) => ident . to_tokens (tokens),
+			Self::Ty (ty) => ty . to_tokens (tokens),
+			Self::Expr (expr) => expr . to_tokens (tokens),
+			Self::Path (path) => path . to_tokens (tokens),
+			Self::Tt (tt) => tt . to_tokens (tokens),
+			Self::Lifetime (lifetime) => lifetime . to_tokens (tokens),
+			Self::Literal (minus_token, literal) =>
+			{
+				minus_token . to_tokens (tokens);
+				literal . to_tokens (tokens);
+			},
+			Self::Vis (vis) => vis . to_tokens (tokens),
+			Self::Block (block) => block . to_tokens (tokens),
+			Self::Item (item) => item . to_tokens (tokens),
+			Self::Pat (pat) => pat . to_tokens (tokens),
+			Self::Meta (meta) => meta . to_tokens (tokens),
+			Self::Stmt (Stmt::Local (local)) => local_to_tokens (local, tokens),
+			Self::Stmt (stmt) => stmt . to_tokens (tokens)
+		}
+	}
+}
+
+// Bindings compare by their tokens, so that a parameter repeated in a
+// pattern must match the same source text each time, regardless of spans.
+impl PartialEq for FragmentValue
+{
+	fn eq (&self, other: &Self) -> bool
+	{
+		self . fragment () == other . fragment ()
+			&& self . to_token_stream () . to_string ()
+				== other . to_token_stream () . to_string ()
+	}
+}
+
+impl Eq for FragmentValue
+{
+}
+
+impl Display for FragmentValue
+{
+	fn fmt (&self, f: &mut Formatter <'_>) -> Result <(), std::fmt::Error>
+	{
+		Display::fmt (&self . to_token_stream (), f)
+	}
+}
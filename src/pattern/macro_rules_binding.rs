@@ -0,0 +1,6 @@
+use super::Fragment;
+
+pub trait MacroRulesBinding
+{
+	fn fragment (&self) -> Option <Fragment>;
+}
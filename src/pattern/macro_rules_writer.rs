@@ -0,0 +1,427 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use proc_macro2::{TokenStream, Group, Punct, Spacing, Span, Delimiter};
+use syn::Ident;
+use quote::{ToTokens, TokenStreamExt};
+
+use super::{
+	Parameter,
+	Index,
+	RepetitionIndex,
+	PatternItem,
+	PatternBuffer,
+	Fragment,
+	MacroRulesBinding,
+	Follower
+};
+
+// Writes a pattern out in `macro_rules!` syntax, either as the matcher of a
+// rule, where parameters need a fragment specifier, or as its transcriber.
+pub (in crate::pattern) struct MacroRulesWriter <T>
+{
+	fragment: Option <fn (&T) -> Option <Fragment>>
+}
+
+impl <T> MacroRulesWriter <T>
+{
+	pub fn matcher () -> Self
+	where T: MacroRulesBinding
+	{
+		Self {fragment: Some (T::fragment)}
+	}
+
+	pub fn transcriber () -> Self
+	{
+		Self {fragment: None}
+	}
+
+	fn write_parameter (&self, parameter: &Parameter <T>, tokens: &mut TokenStream)
+	-> Result <(), NoMacroRulesEquivalent>
+	{
+		parameter . dollar_token . to_tokens (tokens);
+		parameter . ident . to_tokens (tokens);
+
+		if let Some (fragment) = self . fragment
+		{
+			let Some (fragment) = fragment (&parameter . extra_tokens)
+			else
+			{
+				return Err
+				(
+					NoMacroRulesEquivalent::MissingFragment (parameter . ident . clone ())
+				);
+			};
+
+			tokens . append (Punct::new (':', Spacing::Alone));
+			tokens . append (Ident::new (fragment . as_str (), parameter . ident . span ()));
+		}
+
+		Ok (())
+	}
+
+	#[allow (clippy::too_many_arguments)]
+	fn write_repetition
+	(
+		&self,
+		dollar_token: &syn::token::Dollar,
+		repetition_index: Option <&RepetitionIndex>,
+		paren_token: &syn::token::Paren,
+		inner_pattern: &PatternBuffer <T>,
+		interspersed_token: Option <&Punct>,
+		operator: &dyn ToTokens,
+		tokens: &mut TokenStream
+	)
+	-> Result <(), NoMacroRulesEquivalent>
+	{
+		if let Some (repetition_index) = repetition_index
+		{
+			return Err
+			(
+				NoMacroRulesEquivalent::RepetitionIndex (repetition_index . clone ())
+			);
+		}
+
+		let mut inner_tokens = TokenStream::new ();
+		self . write_pattern (inner_pattern, &mut inner_tokens)?;
+
+		dollar_token . to_tokens (tokens);
+		paren_token . surround (tokens, |tokens| tokens . extend (inner_tokens));
+
+		if let Some (punct) = interspersed_token
+		{
+			// Joint spacing would glue the separator onto the operator.
+			let mut separator = Punct::new (punct . as_char (), Spacing::Alone);
+			separator . set_span (punct . span ());
+			tokens . append (separator);
+		}
+
+		operator . to_tokens (tokens);
+
+		Ok (())
+	}
+
+	fn parameter_fragment (&self, parameter: &Parameter <T>) -> Option <Fragment>
+	{
+		self . fragment . and_then (|fragment| fragment (&parameter . extra_tokens))
+	}
+
+	// Joint punctuation is matched as one token, so a run of joint puncts is a
+	// single follower.
+	fn punct_follower (pattern_items: &[PatternItem <T>]) -> Follower
+	{
+		let mut punct_string = String::new ();
+		let mut span = None;
+
+		for pattern_item in pattern_items
+		{
+			let PatternItem::Punct (punct) = pattern_item
+			else
+			{
+				break;
+			};
+
+			punct_string . push (punct . as_char ());
+			span . get_or_insert (punct . span ());
+
+			if punct . spacing () == Spacing::Alone
+			{
+				break;
+			}
+		}
+
+		Follower::Punct (punct_string, span . unwrap_or_else (Span::call_site))
+	}
+
+	// The tokens that can come first when matching `pattern_items`, including
+	// `after` if all of them can match nothing.
+	fn first_set (&self, pattern_items: &[PatternItem <T>], after: &[Follower])
+	-> Vec <Follower>
+	{
+		let mut first_set = Vec::new ();
+
+		for (i, pattern_item) in pattern_items . iter () . enumerate ()
+		{
+			match pattern_item
+			{
+				PatternItem::Parameter (parameter) =>
+				{
+					if let Some (fragment) = self . parameter_fragment (parameter)
+					{
+						first_set . push (Follower::Fragment (parameter . ident . clone (), fragment));
+					}
+
+					return first_set;
+				},
+				PatternItem::Optional (optional) => first_set . extend
+				(
+					self . first_set (optional . inner_pattern . pattern_items (), &[])
+				),
+				PatternItem::ZeroOrMore (zero_or_more) => first_set . extend
+				(
+					self . first_set (zero_or_more . inner_pattern . pattern_items (), &[])
+				),
+				PatternItem::OneOrMore (one_or_more) =>
+				{
+					first_set . extend
+					(
+						self . first_set (one_or_more . inner_pattern . pattern_items (), &[])
+					);
+
+					return first_set;
+				},
+				PatternItem::Group (group) =>
+				{
+					first_set . push (Follower::Group (group . delimiter, group . delim_span . open ()));
+					return first_set;
+				},
+				PatternItem::Ident (ident) =>
+				{
+					first_set . push (Follower::Ident (ident . clone ()));
+					return first_set;
+				},
+				PatternItem::Punct (_) =>
+				{
+					first_set . push (Self::punct_follower (&pattern_items [i ..]));
+					return first_set;
+				},
+				PatternItem::Literal (literal) =>
+				{
+					first_set . push (Follower::Literal (literal . clone ()));
+					return first_set;
+				},
+				// Neither can be written as macro_rules anyway.
+				PatternItem::Index (_) | PatternItem::Alternation (_) => return first_set
+			}
+		}
+
+		first_set . extend (after . iter () . cloned ());
+		first_set
+	}
+
+	fn check_repetition
+	(
+		&self,
+		inner_pattern: &PatternBuffer <T>,
+		interspersed_token: Option <&Punct>,
+		mut after: Vec <Follower>
+	)
+	-> Result <(), NoMacroRulesEquivalent>
+	{
+		match interspersed_token
+		{
+			Some (punct) =>
+				after . push (Follower::Punct (punct . as_char () . to_string (), punct . span ())),
+			None => after . extend (self . first_set (inner_pattern . pattern_items (), &[]))
+		}
+
+		self . check_follow_sets (inner_pattern . pattern_items (), &after)
+	}
+
+	// macro_rules rejects matchers where a fragment is followed by a token
+	// outside its follow set, so a matcher written out here has to as well.
+	pub fn check_follow_sets (&self, pattern_items: &[PatternItem <T>], after: &[Follower])
+	-> Result <(), NoMacroRulesEquivalent>
+	{
+		for (i, pattern_item) in pattern_items . iter () . enumerate ()
+		{
+			let rest = &pattern_items [i + 1 ..];
+
+			match pattern_item
+			{
+				PatternItem::Parameter (parameter) =>
+					if let Some (fragment) = self . parameter_fragment (parameter)
+				{
+					let follower = self
+						. first_set (rest, after)
+						. into_iter ()
+						. find (|follower| ! follower . may_follow (fragment));
+
+					if let Some (follower) = follower
+					{
+						return Err
+						(
+							NoMacroRulesEquivalent::IllegalFollower
+							(
+								parameter . ident . clone (),
+								fragment,
+								follower
+							)
+						);
+					}
+				},
+				PatternItem::Optional (optional) => self . check_follow_sets
+				(
+					optional . inner_pattern . pattern_items (),
+					&self . first_set (rest, after)
+				)?,
+				PatternItem::ZeroOrMore (zero_or_more) => self . check_repetition
+				(
+					&zero_or_more . inner_pattern,
+					zero_or_more . interspersed_token . as_ref (),
+					self . first_set (rest, after)
+				)?,
+				PatternItem::OneOrMore (one_or_more) => self . check_repetition
+				(
+					&one_or_more . inner_pattern,
+					one_or_more . interspersed_token . as_ref (),
+					self . first_set (rest, after)
+				)?,
+				PatternItem::Group (group) => self . check_follow_sets
+				(
+					group . inner_pattern . pattern_items (),
+					&[Follower::End]
+				)?,
+				_ => {}
+			}
+		}
+
+		Ok (())
+	}
+
+	pub fn write_pattern (&self, pattern_buffer: &PatternBuffer <T>, tokens: &mut TokenStream)
+	-> Result <(), NoMacroRulesEquivalent>
+	{
+		for pattern_item in pattern_buffer . pattern_items ()
+		{
+			match pattern_item
+			{
+				PatternItem::Parameter (parameter) =>
+					self . write_parameter (parameter, tokens)?,
+				PatternItem::Index (index) => return Err
+				(
+					NoMacroRulesEquivalent::Index (index . clone ())
+				),
+				PatternItem::Optional (optional) => self . write_repetition
+				(
+					&optional . dollar_token,
+					None,
+					&optional . paren_token,
+					&optional . inner_pattern,
+					None,
+					&optional . question_token,
+					tokens
+				)?,
+				PatternItem::ZeroOrMore (zero_or_more) => self . write_repetition
+				(
+					&zero_or_more . dollar_token,
+					zero_or_more . repetition_index . as_ref (),
+					&zero_or_more . paren_token,
+					&zero_or_more . inner_pattern,
+					zero_or_more . interspersed_token . as_ref (),
+					&zero_or_more . star_token,
+					tokens
+				)?,
+				PatternItem::OneOrMore (one_or_more) => self . write_repetition
+				(
+					&one_or_more . dollar_token,
+					one_or_more . repetition_index . as_ref (),
+					&one_or_more . paren_token,
+					&one_or_more . inner_pattern,
+					one_or_more . interspersed_token . as_ref (),
+					&one_or_more . plus_token,
+					tokens
+				)?,
+				PatternItem::Alternation (alternation) => return Err
+				(
+					NoMacroRulesEquivalent::Alternation (alternation . paren_token . span . join ())
+				),
+				PatternItem::Group (group) =>
+				{
+					if group . delimiter == Delimiter::None
+					{
+						return Err
+						(
+							NoMacroRulesEquivalent::UndelimitedGroup (group . delim_span . join ())
+						);
+					}
+
+					let mut inner_tokens = TokenStream::new ();
+					self . write_pattern (&group . inner_pattern, &mut inner_tokens)?;
+
+					let mut group_tokens = Group::new (group . delimiter, inner_tokens);
+					group_tokens . set_span (group . delim_span . join ());
+					tokens . append (group_tokens);
+				},
+				PatternItem::Ident (ident) => ident . to_tokens (tokens),
+				PatternItem::Punct (punct) => punct . to_tokens (tokens),
+				PatternItem::Literal (literal) => literal . to_tokens (tokens)
+			}
+		}
+
+		Ok (())
+	}
+}
+
+#[derive (Clone, Debug)]
+pub enum NoMacroRulesEquivalent
+{
+	Index (Index),
+	RepetitionIndex (RepetitionIndex),
+	Alternation (Span),
+	UndelimitedGroup (Span),
+	MissingFragment (Ident),
+	IllegalFollower (Ident, Fragment, Follower)
+}
+
+impl Display for NoMacroRulesEquivalent
+{
+	fn fmt (&self, f: &mut Formatter <'_>) -> Result <(), std::fmt::Error>
+	{
+		match self
+		{
+			Self::Index (index) => f . write_fmt
+			(
+				format_args! ("Index `{}` has no macro_rules equivalent", index)
+			),
+			Self::RepetitionIndex (repetition_index) => f . write_fmt
+			(
+				format_args!
+				(
+					"Repetition index `{}` has no macro_rules equivalent",
+					repetition_index . ident
+				)
+			),
+			Self::Alternation (_) =>
+				f . write_str ("Alternations have no macro_rules equivalent"),
+			Self::UndelimitedGroup (_) =>
+				f . write_str ("Undelimited groups have no macro_rules equivalent"),
+			Self::MissingFragment (ident) => f . write_fmt
+			(
+				format_args! ("Parameter `{}` has no macro_rules fragment specifier", ident)
+			),
+			Self::IllegalFollower (ident, fragment, follower) => f . write_fmt
+			(
+				format_args!
+				(
+					"`${}: {}` is followed by {}, which macro_rules does not allow after `{}` fragments",
+					ident,
+					fragment,
+					follower,
+					fragment
+				)
+			)
+		}
+	}
+}
+
+impl Error for NoMacroRulesEquivalent
+{
+}
+
+impl Into <syn::Error> for NoMacroRulesEquivalent
+{
+	fn into (self) -> syn::Error
+	{
+		match &self
+		{
+			Self::Index (index) => syn::Error::new_spanned (index, &self),
+			Self::RepetitionIndex (repetition_index) =>
+				syn::Error::new_spanned (repetition_index, &self),
+			Self::Alternation (span) | Self::UndelimitedGroup (span) =>
+				syn::Error::new (*span, &self),
+			Self::MissingFragment (ident) => syn::Error::new_spanned (ident, &self),
+			Self::IllegalFollower (_, _, follower) => syn::Error::new (follower . span (), &self)
+		}
+	}
+}
@@ -1,4 +1,6 @@
 use std::borrow::Borrow;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::fmt::Display;
 use std::marker::PhantomData;
 
@@ -13,11 +15,19 @@ use super::{
 	Parameter,
 	Index,
 	StructuredBindings,
+	ParameterBindingNotFound,
+	IndexBindings,
+	OptionalPattern,
+	AlternationPattern,
+	GroupPattern,
+	PatternItem,
+	PatternBuffer,
 	VisitationError,
 	PatternVisitor,
 	OptionalVisitor,
 	ZeroOrMoreVisitor,
 	OneOrMoreVisitor,
+	AlternationVisitor,
 	ParseBinding
 };
 
@@ -56,6 +66,7 @@ where
 	type OptionalVisitor = MatchOptionalVisitor <'a, ParseBuffer <'a>, V>;
 	type ZeroOrMoreVisitor = MatchXOrMoreVisitor <'a, ParseBuffer <'a>, V>;
 	type OneOrMoreVisitor = MatchXOrMoreVisitor <'a, ParseBuffer <'a>, V>;
+	type AlternationVisitor = MatchAlternationVisitor <'a, ParseBuffer <'a>, V>;
 	type GroupVisitor = MatchVisitor <'a, ParseBuffer <'a>, V>;
 
 	fn visit_parameter (&mut self, parameter: &Parameter <T>)
@@ -188,6 +199,46 @@ where
 		Ok (())
 	}
 
+	fn pre_visit_alternation <'b, I> (&mut self, _alternation_parameters: I)
+	-> Result <Self::AlternationVisitor, Self::Error>
+	where I: IntoIterator <Item = &'b Ident>
+	{
+		Ok (Self::AlternationVisitor::new (self . input . borrow () . fork ()))
+	}
+
+	fn post_visit_alternation <'b, I>
+	(
+		&mut self,
+		alternation_parameters: I,
+		alternation_visitor: Self::AlternationVisitor
+	)
+	-> Result <(), Self::Error>
+	where I: IntoIterator <Item = &'b Ident>
+	{
+		let Some ((branch, branch_bindings)) = alternation_visitor . matched
+		else
+		{
+			return Err
+			(
+				alternation_visitor
+					. error
+					. unwrap_or_else (|| self . input . borrow () . error ("no alternative matched"))
+			);
+		};
+
+		self . input . borrow () . advance_to (&alternation_visitor . input);
+
+		self . bindings . add_alternation_bindings
+		(
+			alternation_parameters,
+			branch,
+			branch_bindings
+		)
+			. map_err (Into::<syn::Error>::into)?;
+
+		Ok (())
+	}
+
 	fn pre_visit_group
 	(
 		&mut self,
@@ -494,3 +545,645 @@ where
 		}
 	}
 }
+
+pub struct MatchAlternationVisitor <'a, S, V>
+{
+	input: S,
+	input_lifetime: PhantomData <&'a S>,
+	matched: Option <(usize, StructuredBindings <V>)>,
+	error: Option <syn::Error>
+}
+
+impl <'a, S, V> MatchAlternationVisitor <'a, S, V>
+{
+	fn new (input: S) -> Self
+	{
+		Self
+		{
+			input,
+			input_lifetime: PhantomData,
+			matched: None,
+			error: None
+		}
+	}
+}
+
+impl <'a, S, V, T> AlternationVisitor <T> for MatchAlternationVisitor <'a, S, V>
+where
+	S: Borrow <ParseBuffer <'a>>,
+	V: Clone + PartialEq + Display,
+	T: ParseBinding <V>
+{
+	type Error = syn::Error;
+	type BranchVisitor = MatchVisitor <'a, ParseBuffer <'a>, V>;
+
+	fn pre_visit_branch (&mut self, _branch: usize)
+	-> Result <Option <Self::BranchVisitor>, Self::Error>
+	{
+		Ok (Some (Self::BranchVisitor::new (self . input . borrow () . fork ())))
+	}
+
+	// The first branch that matches is taken.  If none do, the errors from
+	// every branch are reported together.
+	fn post_visit_branch
+	(
+		&mut self,
+		branch: usize,
+		branch_visitor: Self::BranchVisitor,
+		visit_result: Result <(), VisitationError <Self::Error>>
+	)
+	-> Result <bool, VisitationError <Self::Error>>
+	{
+		match visit_result
+		{
+			Ok (()) =>
+			{
+				self . input . borrow () . advance_to (&branch_visitor . input);
+				self . matched = Some ((branch, branch_visitor . bindings));
+
+				Ok (true)
+			},
+			Err (VisitationError::Visitor (error)) =>
+			{
+				match &mut self . error
+				{
+					Some (errors) => errors . combine (error),
+					None => self . error = Some (error)
+				}
+
+				Ok (false)
+			},
+			Err (error) => Err (error)
+		}
+	}
+}
+
+type MatchResult <'a, V> =
+	Result <Option <(ParseBuffer <'a>, StructuredBindings <V>)>, VisitationError <syn::Error>>;
+
+type Continuation <'c, 'a, V> =
+	dyn FnMut (ParseBuffer <'a>, StructuredBindings <V>) -> MatchResult <'a, V> + 'c;
+
+struct Repetition <'p, T>
+{
+	inner_pattern: &'p PatternBuffer <T>,
+	repetition_index: Option <&'p Ident>,
+	interspersed_token: Option <&'p Punct>,
+	at_least_one: bool
+}
+
+// Matches depth first, trying the most iterations of a repetition (and the
+// present branch of an optional) first, and falls back to fewer whenever the
+// rest of the pattern fails to match.  Every pattern item tried costs one
+// step, and matching stops with an error once the step budget is spent.
+pub struct BacktrackingMatcher
+{
+	step_budget: usize,
+	steps: Cell <usize>,
+	speculation_depth: Cell <usize>,
+	first_error: RefCell <Option <syn::Error>>
+}
+
+impl BacktrackingMatcher
+{
+	pub fn new (step_budget: usize) -> Self
+	{
+		Self
+		{
+			step_budget,
+			steps: Cell::new (0),
+			speculation_depth: Cell::new (0),
+			first_error: RefCell::new (None)
+		}
+	}
+
+	pub fn match_pattern <'a, T, V>
+	(
+		&self,
+		pattern_buffer: &PatternBuffer <T>,
+		input: &ParseBuffer <'a>,
+		require_end: bool
+	)
+	-> Result <StructuredBindings <V>, VisitationError <syn::Error>>
+	where
+		T: ParseBinding <V>,
+		V: Clone + PartialEq + Display
+	{
+		let matched = self . match_items
+		(
+			pattern_buffer . pattern_items (),
+			input . fork (),
+			StructuredBindings::new (),
+			&HashMap::new (),
+			&mut |rest, bindings|
+			{
+				if require_end && ! rest . is_empty ()
+				{
+					return self . fail (rest . error ("expected end of input"));
+				}
+
+				Ok (Some ((rest, bindings)))
+			}
+		)?;
+
+		match matched
+		{
+			Some ((rest, bindings)) =>
+			{
+				input . advance_to (&rest);
+
+				Ok (bindings)
+			},
+			None => Err
+			(
+				VisitationError::Visitor
+				(
+					self
+						. first_error
+						. take ()
+						. unwrap_or_else (|| input . error ("pattern did not match"))
+				)
+			)
+		}
+	}
+
+	// Like the greedy matcher, failures inside an optional iteration are not
+	// reported, since the pattern is free to stop repeating there.
+	fn fail <'a, V> (&self, error: syn::Error) -> MatchResult <'a, V>
+	{
+		if self . speculation_depth . get () == 0
+		{
+			self . first_error . borrow_mut () . get_or_insert (error);
+		}
+
+		Ok (None)
+	}
+
+	fn speculate <R> (&self, speculative: bool, f: impl FnOnce () -> R) -> R
+	{
+		let depth = self . speculation_depth . get ();
+
+		if speculative
+		{
+			self . speculation_depth . set (depth + 1);
+		}
+
+		let result = f ();
+
+		self . speculation_depth . set (depth);
+
+		result
+	}
+
+	fn resume <R> (&self, speculative: bool, f: impl FnOnce () -> R) -> R
+	{
+		let depth = self . speculation_depth . get ();
+
+		if speculative
+		{
+			self . speculation_depth . set (depth - 1);
+		}
+
+		let result = f ();
+
+		self . speculation_depth . set (depth);
+
+		result
+	}
+
+	fn step (&self, input: &ParseBuffer <'_>) -> Result <(), VisitationError <syn::Error>>
+	{
+		self . steps . set (self . steps . get () + 1);
+
+		// `ParseBuffer::error` would blame the end of input when the budget
+		// runs out there, which is not what went wrong.
+		if self . steps . get () > self . step_budget
+		{
+			return Err
+			(
+				VisitationError::Visitor
+				(
+					syn::Error::new
+					(
+						input . span (),
+						format!
+						(
+							"pattern matching gave up after {} steps",
+							self . step_budget
+						)
+					)
+				)
+			);
+		}
+
+		Ok (())
+	}
+
+	fn match_items <'a, T, V>
+	(
+		&self,
+		pattern_items: &[PatternItem <T>],
+		input: ParseBuffer <'a>,
+		mut bindings: StructuredBindings <V>,
+		indices: &HashMap <Ident, usize>,
+		continuation: &mut Continuation <'_, 'a, V>
+	)
+	-> MatchResult <'a, V>
+	where
+		T: ParseBinding <V>,
+		V: Clone + PartialEq + Display
+	{
+		let Some ((pattern_item, rest)) = pattern_items . split_first ()
+		else
+		{
+			return continuation (input, bindings);
+		};
+
+		self . step (&input)?;
+
+		match pattern_item
+		{
+			PatternItem::Optional (optional) => self . match_optional
+			(
+				optional,
+				rest,
+				input,
+				bindings,
+				indices,
+				continuation
+			),
+			PatternItem::ZeroOrMore (zero_or_more) =>
+			{
+				let repetition = Repetition
+				{
+					inner_pattern: &zero_or_more . inner_pattern,
+					repetition_index: zero_or_more . repetition_index . as_ref () . map (|ri| &ri . ident),
+					interspersed_token: zero_or_more . interspersed_token . as_ref (),
+					at_least_one: false
+				};
+
+				self . match_iterations
+				(
+					&repetition,
+					rest,
+					input,
+					bindings,
+					Vec::new (),
+					indices,
+					continuation
+				)
+			},
+			PatternItem::OneOrMore (one_or_more) =>
+			{
+				let repetition = Repetition
+				{
+					inner_pattern: &one_or_more . inner_pattern,
+					repetition_index: one_or_more . repetition_index . as_ref () . map (|ri| &ri . ident),
+					interspersed_token: one_or_more . interspersed_token . as_ref (),
+					at_least_one: true
+				};
+
+				self . match_iterations
+				(
+					&repetition,
+					rest,
+					input,
+					bindings,
+					Vec::new (),
+					indices,
+					continuation
+				)
+			},
+			PatternItem::Alternation (alternation) => self . match_alternation
+			(
+				alternation,
+				rest,
+				input,
+				bindings,
+				indices,
+				continuation
+			),
+			PatternItem::Group (group) => self . match_group
+			(
+				group,
+				rest,
+				input,
+				bindings,
+				indices,
+				continuation
+			),
+			_ =>
+			{
+				let mut visitor = MatchVisitor::<'a, ParseBuffer <'a>, V>::new (input);
+
+				let visit_result = match pattern_item
+				{
+					PatternItem::Index (index) => match indices . get (&index . ident)
+					{
+						Some (i) => <MatchVisitor <'a, ParseBuffer <'a>, V> as PatternVisitor <T>>
+							::visit_index (&mut visitor, index, *i)
+							. map_err (VisitationError::Visitor),
+						None => Err (ParameterBindingNotFound::new (index . ident . clone ()) . into ())
+					},
+					_ => pattern_item . visit (&IndexBindings::new (), &mut visitor)
+				};
+
+				match visit_result
+				{
+					Ok (()) => {},
+					Err (VisitationError::Visitor (error)) => return self . fail (error),
+					Err (error) => return Err (error)
+				}
+
+				if let Err (error) = bindings . merge (visitor . bindings)
+				{
+					return self . fail (error . into ());
+				}
+
+				self . match_items (rest, visitor . input, bindings, indices, continuation)
+			}
+		}
+	}
+
+	fn match_optional <'a, T, V>
+	(
+		&self,
+		optional: &OptionalPattern <T>,
+		rest: &[PatternItem <T>],
+		input: ParseBuffer <'a>,
+		mut bindings: StructuredBindings <V>,
+		indices: &HashMap <Ident, usize>,
+		continuation: &mut Continuation <'_, 'a, V>
+	)
+	-> MatchResult <'a, V>
+	where
+		T: ParseBinding <V>,
+		V: Clone + PartialEq + Display
+	{
+		let matched = self . speculate
+		(
+			true,
+			|| self . match_items
+			(
+				optional . inner_pattern . pattern_items (),
+				input . fork (),
+				StructuredBindings::new (),
+				indices,
+				&mut |after, once_bindings| self . resume
+				(
+					true,
+					||
+					{
+						let mut bindings = bindings . clone ();
+
+						if let Err (error) = bindings . add_optional_bindings
+						(
+							optional . inner_pattern . referenced_identifiers (),
+							Some (once_bindings)
+						)
+						{
+							return self . fail (error . into ());
+						}
+
+						self . match_items (rest, after, bindings, indices, &mut *continuation)
+					}
+				)
+			)
+		)?;
+
+		if matched . is_some ()
+		{
+			return Ok (matched);
+		}
+
+		if let Err (error) = bindings . add_optional_bindings
+		(
+			optional . inner_pattern . referenced_identifiers (),
+			None
+		)
+		{
+			return self . fail (error . into ());
+		}
+
+		self . match_items (rest, input, bindings, indices, continuation)
+	}
+
+	#[allow (clippy::too_many_arguments)]
+	fn match_iterations <'a, T, V>
+	(
+		&self,
+		repetition: &Repetition <'_, T>,
+		rest: &[PatternItem <T>],
+		input: ParseBuffer <'a>,
+		mut bindings: StructuredBindings <V>,
+		iterations: Vec <StructuredBindings <V>>,
+		indices: &HashMap <Ident, usize>,
+		continuation: &mut Continuation <'_, 'a, V>
+	)
+	-> MatchResult <'a, V>
+	where
+		T: ParseBinding <V>,
+		V: Clone + PartialEq + Display
+	{
+		let iteration_input = input . fork ();
+
+		let separated = iterations . is_empty () || match repetition . interspersed_token
+		{
+			Some (punct) => matches!
+			(
+				iteration_input . parse::<Punct> (),
+				Ok (input_punct) if input_punct . as_char () == punct . as_char ()
+			),
+			None => true
+		};
+
+		if separated
+		{
+			let mut iteration_indices = indices . clone ();
+
+			if let Some (ident) = repetition . repetition_index
+			{
+				iteration_indices . insert (ident . clone (), iterations . len ());
+			}
+
+			let start = input . cursor ();
+
+			let speculative = ! repetition . at_least_one || ! iterations . is_empty ();
+
+			let matched = self . speculate
+			(
+				speculative,
+				|| self . match_items
+				(
+					repetition . inner_pattern . pattern_items (),
+					iteration_input,
+					StructuredBindings::new (),
+					&iteration_indices,
+					&mut |after, iteration_bindings|
+					{
+						// An iteration that consumes nothing would repeat forever.
+						if after . cursor () == start
+						{
+							return Ok (None);
+						}
+
+						let mut iterations = iterations . clone ();
+						iterations . push (iteration_bindings);
+
+						self . resume
+						(
+							speculative,
+							|| self . match_iterations
+							(
+								repetition,
+								rest,
+								after,
+								bindings . clone (),
+								iterations,
+								indices,
+								&mut *continuation
+							)
+						)
+					}
+				)
+			)?;
+
+			if matched . is_some ()
+			{
+				return Ok (matched);
+			}
+		}
+
+		if repetition . at_least_one && iterations . is_empty ()
+		{
+			return Ok (None);
+		}
+
+		if let Some (ident) = repetition . repetition_index
+			&& let Err (error) = bindings . add_index_len (ident . clone (), iterations . len ())
+		{
+			return self . fail (error . into ());
+		}
+
+		let idents = repetition . inner_pattern . referenced_identifiers ();
+
+		let added =
+			if repetition . at_least_one
+			{
+				bindings . add_one_or_more_bindings (idents, iterations)
+			}
+			else
+			{
+				bindings . add_zero_or_more_bindings (idents, iterations)
+			};
+
+		if let Err (error) = added
+		{
+			return self . fail (error . into ());
+		}
+
+		self . match_items (rest, input, bindings, indices, continuation)
+	}
+
+	fn match_alternation <'a, T, V>
+	(
+		&self,
+		alternation: &AlternationPattern <T>,
+		rest: &[PatternItem <T>],
+		input: ParseBuffer <'a>,
+		bindings: StructuredBindings <V>,
+		indices: &HashMap <Ident, usize>,
+		continuation: &mut Continuation <'_, 'a, V>
+	)
+	-> MatchResult <'a, V>
+	where
+		T: ParseBinding <V>,
+		V: Clone + PartialEq + Display
+	{
+		for (branch, branch_pattern) in alternation . branches . iter () . enumerate ()
+		{
+			let matched = self . match_items
+			(
+				branch_pattern . pattern_items (),
+				input . fork (),
+				StructuredBindings::new (),
+				indices,
+				&mut |after, branch_bindings|
+				{
+					let mut bindings = bindings . clone ();
+
+					if let Err (error) = bindings . add_alternation_bindings
+					(
+						alternation . referenced_identifiers (),
+						branch,
+						branch_bindings
+					)
+					{
+						return self . fail (error . into ());
+					}
+
+					self . match_items (rest, after, bindings, indices, &mut *continuation)
+				}
+			)?;
+
+			if matched . is_some ()
+			{
+				return Ok (matched);
+			}
+		}
+
+		Ok (None)
+	}
+
+	fn match_group <'a, T, V>
+	(
+		&self,
+		group: &GroupPattern <T>,
+		rest: &[PatternItem <T>],
+		input: ParseBuffer <'a>,
+		bindings: StructuredBindings <V>,
+		indices: &HashMap <Ident, usize>,
+		continuation: &mut Continuation <'_, 'a, V>
+	)
+	-> MatchResult <'a, V>
+	where
+		T: ParseBinding <V>,
+		V: Clone + PartialEq + Display
+	{
+		let mut visitor = MatchVisitor::<'a, ParseBuffer <'a>, V>::new (input);
+
+		let group_visitor =
+			match <MatchVisitor <'a, ParseBuffer <'a>, V> as PatternVisitor <T>>
+				::pre_visit_group (&mut visitor, group . delimiter, group . delim_span)
+		{
+			Ok (group_visitor) => group_visitor,
+			Err (error) => return self . fail (error)
+		};
+
+		let outer_input = visitor . input;
+
+		self . match_items
+		(
+			group . inner_pattern . pattern_items (),
+			group_visitor . input,
+			StructuredBindings::new (),
+			indices,
+			&mut |content, group_bindings|
+			{
+				if ! content . is_empty ()
+				{
+					return self . fail (content . error ("expected end of input"));
+				}
+
+				let mut bindings = bindings . clone ();
+
+				if let Err (error) = bindings . merge (group_bindings)
+				{
+					return self . fail (error . into ());
+				}
+
+				self . match_items (rest, outer_input . fork (), bindings, indices, &mut *continuation)
+			}
+		)
+	}
+}
@@ -15,6 +15,9 @@ use index_bindings::*;
 mod repetition_pattern;
 pub use repetition_pattern::*;
 
+mod alternation_pattern;
+pub use alternation_pattern::*;
+
 mod group_pattern;
 use group_pattern::*;
 
@@ -53,6 +56,20 @@ pub use pattern::*;
 mod type_annotation;
 pub use type_annotation::*;
 
+mod fragment;
+pub use fragment::*;
+
+mod macro_rules_binding;
+pub use macro_rules_binding::*;
+
+mod macro_rules_writer;
+use macro_rules_writer::*;
+
+mod follow_set;
+pub use follow_set::*;
+
 mod expect;
 pub use expect::*;
 
+#[cfg (test)]
+mod tests;
@@ -15,11 +15,24 @@ use super::{
 	PatternVisitor,
 	ParseBinding,
 	MatchVisitor,
+	BacktrackingMatcher,
 	TokenizeBinding,
 	SubstitutionVisitor,
-	SubstitutionError
+	SubstitutionError,
+	MacroRulesBinding,
+	MacroRulesWriter,
+	NoMacroRulesEquivalent,
+	Follower
 };
 
+#[derive (Copy, Clone, Debug, Default)]
+pub enum MatchStrategy
+{
+	#[default]
+	Greedy,
+	Backtracking {step_budget: usize}
+}
+
 #[derive (Clone, Debug)]
 pub struct Pattern <T>
 {
@@ -94,22 +107,56 @@ impl <T> Pattern <T>
 		T: ParseBinding <V>,
 		V: Clone + PartialEq + Display
 	{
-		let mut match_visitor = MatchVisitor::new (input);
+		self . match_input_with (input, MatchStrategy::Greedy)
+	}
+
+	pub fn match_input_with <V> (&self, input: ParseStream <'_>, strategy: MatchStrategy)
+	-> Result <StructuredBindings <V>, VisitationError <syn::Error>>
+	where
+		T: ParseBinding <V>,
+		V: Clone + PartialEq + Display
+	{
+		match strategy
+		{
+			MatchStrategy::Greedy =>
+			{
+				let mut match_visitor = MatchVisitor::new (input);
 
-		self . visit_pattern (&mut match_visitor)?;
+				self . visit_pattern (&mut match_visitor)?;
 
-		Ok (match_visitor . into_bindings ())
+				Ok (match_visitor . into_bindings ())
+			},
+			MatchStrategy::Backtracking {step_budget} => BacktrackingMatcher::new (step_budget)
+				. match_pattern (&self . pattern_buffer, input, false)
+		}
 	}
 
 	pub fn match_tokens <V> (&self, tokens: TokenStream)
 	-> syn::Result <StructuredBindings <V>>
+	where
+		T: ParseBinding <V>,
+		V: Clone + PartialEq + Display
+	{
+		self . match_tokens_with (tokens, MatchStrategy::Greedy)
+	}
+
+	pub fn match_tokens_with <V> (&self, tokens: TokenStream, strategy: MatchStrategy)
+	-> syn::Result <StructuredBindings <V>>
 	where
 		T: ParseBinding <V>,
 		V: Clone + PartialEq + Display
 	{
 		let parser = |input: ParseStream <'_>|
 		{
-			self . match_input (input) . map_err (Into::into)
+			match strategy
+			{
+				MatchStrategy::Greedy => self . match_input (input),
+				// Backtracking has to know that the whole stream must be
+				// consumed, otherwise it settles for the first prefix match.
+				MatchStrategy::Backtracking {step_budget} => BacktrackingMatcher::new (step_budget)
+					. match_pattern (&self . pattern_buffer, input, true)
+			}
+				. map_err (Into::into)
 		};
 
 		parser . parse2 (tokens)
@@ -151,4 +198,25 @@ impl <T> Pattern <T>
 
 		Ok (Self {pattern_buffer, parameters})
 	}
+
+	pub fn to_macro_rules_matcher (&self) -> Result <TokenStream, NoMacroRulesEquivalent>
+	where T: MacroRulesBinding
+	{
+		let mut tokens = TokenStream::new ();
+		let writer = MacroRulesWriter::matcher ();
+
+		writer . write_pattern (&self . pattern_buffer, &mut tokens)?;
+		writer . check_follow_sets (self . pattern_buffer . pattern_items (), &[Follower::End])?;
+
+		Ok (tokens)
+	}
+
+	pub fn to_macro_rules_transcriber (&self) -> Result <TokenStream, NoMacroRulesEquivalent>
+	{
+		let mut tokens = TokenStream::new ();
+
+		MacroRulesWriter::transcriber () . write_pattern (&self . pattern_buffer, &mut tokens)?;
+
+		Ok (tokens)
+	}
 }
@@ -16,6 +16,7 @@ use super::{
 	OneOrMorePattern,
 	RepetitionPattern,
 	NoParameterInRepetition,
+	AlternationPattern,
 	GroupPattern,
 	PatternItem,
 	VisitationError,
@@ -60,6 +61,11 @@ impl <T> PatternBuffer <T>
 		}
 	}
 
+	pub fn pattern_items (&self) -> &[PatternItem <T>]
+	{
+		&self . pattern_items
+	}
+
 	pub fn append_parameter (&mut self, parameter: Parameter <T>)
 	{
 		self . parameters . insert (parameter . ident . clone ());
@@ -113,6 +119,12 @@ impl <T> PatternBuffer <T>
 		}
 	}
 
+	pub fn append_alternation (&mut self, alternation: AlternationPattern <T>)
+	{
+		self . sub_pattern_indices . push (self . pattern_items . len ());
+		self . pattern_items . push (PatternItem::Alternation (alternation));
+	}
+
 	pub fn append_group (&mut self, group: GroupPattern <T>)
 	{
 		self . sub_pattern_indices . push (self . pattern_items . len ());
@@ -147,6 +159,8 @@ impl <T> PatternBuffer <T>
 				self . append_zero_or_more (zero_or_more),
 			PatternItem::OneOrMore (one_or_more) =>
 				self . append_one_or_more (one_or_more),
+			PatternItem::Alternation (alternation) =>
+				self . append_alternation (alternation),
 			PatternItem::Group (group) => self . append_group (group),
 			PatternItem::Ident (ident) => self . append_ident (ident),
 			PatternItem::Punct (punct) => self . append_punct (punct),
@@ -163,6 +177,7 @@ impl <T> PatternBuffer <T>
 			PatternItem::Optional (optional) => Box::new (optional . referenced_identifiers ()),
 			PatternItem::ZeroOrMore (zero_or_more) => Box::new (zero_or_more . referenced_identifiers ()),
 			PatternItem::OneOrMore (one_or_more) => Box::new (one_or_more . referenced_identifiers ()),
+			PatternItem::Alternation (alternation) => Box::new (alternation . referenced_identifiers ()),
 			PatternItem::Group (group) => Box::new (group . referenced_identifiers ()),
 			_ => unreachable! ()
 		}
@@ -20,6 +20,7 @@ use super::{
 	RepetitionPattern,
 	NoParameterInRepetition,
 	RepetitionLenMismatch,
+	AlternationPattern,
 	GroupPattern,
 	PatternBuffer,
 	PatternVisitor,
@@ -34,6 +35,7 @@ pub enum PatternItem <T>
 	Optional (OptionalPattern <T>),
 	ZeroOrMore (ZeroOrMorePattern <T>),
 	OneOrMore (OneOrMorePattern <T>),
+	Alternation (AlternationPattern <T>),
 	Group (GroupPattern <T>),
 	Ident (Ident),
 	Punct (Punct),
@@ -55,6 +57,10 @@ where T: Parse
 			{
 				Ok (Self::Index (input . parse ()?))
 			}
+			else if AlternationPattern::<T>::peek (input)
+			{
+				Ok (Self::Alternation (input . parse ()?))
+			}
 			else if input . peek2 (syn::token::Bracket) || input . peek2 (syn::token::Paren)
 			{
 				let repetition: RepetitionPattern <T> = input . parse ()?;
@@ -105,6 +111,7 @@ impl <T> PatternItem <T>
 			Self::Optional (optional) => optional . validate (),
 			Self::ZeroOrMore (zero_or_more) => zero_or_more . validate (),
 			Self::OneOrMore (one_or_more) => one_or_more . validate (),
+			Self::Alternation (alternation) => alternation . validate (),
 			Self::Group (group) => group . validate (),
 			_ => Ok (())
 		}
@@ -132,6 +139,8 @@ impl <T> PatternItem <T>
 				. visit (index_bindings, visitor),
 			Self::OneOrMore (one_or_more) => one_or_more
 				. visit (index_bindings, visitor),
+			Self::Alternation (alternation) => alternation
+				. visit (index_bindings, visitor),
 			Self::Group (group) => group . visit (index_bindings, visitor),
 			Self::Ident (ident) => visitor
 				. visit_ident (ident)
@@ -206,6 +215,8 @@ impl <T> PatternItem <T>
 				zero_or_more . specialize (index_bindings, bindings, pattern_buffer),
 			Self::OneOrMore (one_or_more) =>
 				one_or_more . specialize (index_bindings, bindings, pattern_buffer),
+			Self::Alternation (alternation) =>
+				alternation . specialize (index_bindings, bindings, pattern_buffer),
 			Self::Group (group) =>
 				group . specialize (index_bindings, bindings, pattern_buffer),
 			Self::Ident (ident) =>
@@ -230,6 +241,7 @@ where T: ToTokens
 			Self::Optional (optional) => optional . to_tokens (tokens),
 			Self::ZeroOrMore (zero_or_more) => zero_or_more . to_tokens (tokens),
 			Self::OneOrMore (one_or_more) => one_or_more . to_tokens (tokens),
+			Self::Alternation (alternation) => alternation . to_tokens (tokens),
 			Self::Group (group) => group . to_tokens (tokens),
 			Self::Ident (ident) => ident . to_tokens (tokens),
 			Self::Punct (punct) => punct . to_tokens (tokens),
@@ -238,6 +250,27 @@ where T: ToTokens
 	}
 }
 
+impl <T> From <RepetitionPattern <T>> for PatternItem <T>
+{
+	fn from (repetition: RepetitionPattern <T>) -> Self
+	{
+		match repetition
+		{
+			RepetitionPattern::Optional (optional) => Self::Optional (optional),
+			RepetitionPattern::ZeroOrMore (zero_or_more) => Self::ZeroOrMore (zero_or_more),
+			RepetitionPattern::OneOrMore (one_or_more) => Self::OneOrMore (one_or_more)
+		}
+	}
+}
+
+impl <T> From <AlternationPattern <T>> for PatternItem <T>
+{
+	fn from (alternation: AlternationPattern <T>) -> Self
+	{
+		Self::Alternation (alternation)
+	}
+}
+
 #[derive (Clone, Debug)]
 pub enum VisitationError <E>
 {
@@ -11,6 +11,7 @@ pub trait PatternVisitor <T>
 	type OptionalVisitor: OptionalVisitor <T, Error = Self::Error>;
 	type ZeroOrMoreVisitor: ZeroOrMoreVisitor <T, Error = Self::Error>;
 	type OneOrMoreVisitor: OneOrMoreVisitor <T, Error = Self::Error>;
+	type AlternationVisitor: AlternationVisitor <T, Error = Self::Error>;
 
 	type GroupVisitor: PatternVisitor <T, Error = Self::Error>;
 
@@ -81,6 +82,23 @@ pub trait PatternVisitor <T>
 		Ok (())
 	}
 
+	fn pre_visit_alternation <'a, I> (&mut self, alternation_parameters: I)
+	-> Result <Self::AlternationVisitor, Self::Error>
+	where I: IntoIterator <Item = &'a Ident>;
+
+	#[allow (unused_variables)]
+	fn post_visit_alternation <'a, I>
+	(
+		&mut self,
+		alternation_parameters: I,
+		alternation_visitor: Self::AlternationVisitor
+	)
+	-> Result <(), Self::Error>
+	where I: IntoIterator <Item = &'a Ident>
+	{
+		Ok (())
+	}
+
 	fn pre_visit_group
 	(
 		&mut self,
@@ -197,3 +215,26 @@ pub trait OneOrMoreVisitor <T>
 	#[allow (unused_variables)]
 	fn visit_maybe_punct (&mut self, punct: &Punct) -> Result <bool, Self::Error>;
 }
+
+pub trait AlternationVisitor <T>
+{
+	type Error;
+	type BranchVisitor: PatternVisitor <T, Error = Self::Error>;
+
+	fn pre_visit_branch (&mut self, branch: usize)
+	-> Result <Option <Self::BranchVisitor>, Self::Error>;
+
+	// Returns whether the remaining branches should be skipped.
+	#[allow (unused_variables)]
+	fn post_visit_branch
+	(
+		&mut self,
+		branch: usize,
+		branch_visitor: Self::BranchVisitor,
+		visit_result: Result <(), VisitationError <Self::Error>>
+	)
+	-> Result <bool, VisitationError <Self::Error>>
+	{
+		Ok (true)
+	}
+}
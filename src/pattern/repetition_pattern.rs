@@ -13,6 +13,7 @@ use super::{
 	VisitationError,
 	SpecializationError,
 	PatternBuffer,
+	PatternItem,
 	PatternVisitor,
 	OptionalVisitor,
 	ZeroOrMoreVisitor,
@@ -941,14 +942,14 @@ where T: ToTokens
 #[derive (Clone, Debug)]
 pub struct NoParameterInRepetition <T>
 {
-	repetition: RepetitionPattern <T>
+	pattern_item: Box <PatternItem <T>>
 }
 
 impl <T> NoParameterInRepetition <T>
 {
-	pub fn new (repetition: RepetitionPattern <T>) -> Self
+	pub fn new (pattern_item: impl Into <PatternItem <T>>) -> Self
 	{
-		Self {repetition}
+		Self {pattern_item: Box::new (pattern_item . into ())}
 	}
 }
 
@@ -956,7 +957,11 @@ impl <T> Display for NoParameterInRepetition <T>
 {
 	fn fmt (&self, f: &mut Formatter <'_>) -> Result <(), std::fmt::Error>
 	{
-		f . write_str ("No parameter in repetition")
+		match *self . pattern_item
+		{
+			PatternItem::Alternation (_) => f . write_str ("No parameter in alternation"),
+			_ => f . write_str ("No parameter in repetition")
+		}
 	}
 }
 
@@ -970,7 +975,7 @@ where T: ToTokens
 {
 	fn into (self) -> syn::Error
 	{
-		syn::Error::new_spanned (&self . repetition, &self)
+		syn::Error::new_spanned (&self . pattern_item, &self)
 	}
 }
 
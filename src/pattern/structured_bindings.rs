@@ -12,7 +12,8 @@ pub enum StructuredBindingType
 	Index,
 	Optional,
 	ZeroOrMore,
-	OneOrMore
+	OneOrMore,
+	Alternation
 }
 
 impl Display for StructuredBindingType
@@ -25,7 +26,8 @@ impl Display for StructuredBindingType
 			Self::Index => f . write_str ("index"),
 			Self::Optional => f . write_str ("optional"),
 			Self::ZeroOrMore => f . write_str ("zero_or_more"),
-			Self::OneOrMore => f . write_str ("one_or_more")
+			Self::OneOrMore => f . write_str ("one_or_more"),
+			Self::Alternation => f . write_str ("alternation")
 		}
 	}
 }
@@ -37,7 +39,8 @@ pub enum StructuredBinding <V>
 	Index (usize),
 	Optional (Option <Box <StructuredBinding <V>>>),
 	ZeroOrMore (Vec <StructuredBinding <V>>),
-	OneOrMore (Vec <StructuredBinding <V>>)
+	OneOrMore (Vec <StructuredBinding <V>>),
+	Alternation (usize, Option <Box <StructuredBinding <V>>>)
 }
 
 impl <V> StructuredBinding <V>
@@ -50,7 +53,8 @@ impl <V> StructuredBinding <V>
 			Self::Index (_) => StructuredBindingType::Index,
 			Self::Optional (_) => StructuredBindingType::Optional,
 			Self::ZeroOrMore (_) => StructuredBindingType::ZeroOrMore,
-			Self::OneOrMore (_) => StructuredBindingType::OneOrMore
+			Self::OneOrMore (_) => StructuredBindingType::OneOrMore,
+			Self::Alternation (..) => StructuredBindingType::Alternation
 		}
 	}
 
@@ -84,6 +88,15 @@ impl <V> StructuredBinding <V>
 					. into_iter ()
 					. map (|binding| binding . map::<&mut FF, FF, O> (f . borrow_mut ()))
 					. collect ()
+			),
+			Self::Alternation (branch, option) => StructuredBinding::Alternation
+			(
+				branch,
+				option . map
+				(
+					|boxed_v|
+					Box::new ((*boxed_v) . map::<&mut FF, FF, O> (f . borrow_mut ()))
+				)
 			)
 		}
 	}
@@ -131,7 +144,15 @@ where V: Display
 				None => f . write_str ("None")
 			},
 			Self::ZeroOrMore (vec) => write_vec (vec, f),
-			Self::OneOrMore (vec) => write_vec (vec, f)
+			Self::OneOrMore (vec) => write_vec (vec, f),
+			Self::Alternation (branch, option) => match option
+			{
+				Some (boxed_binding) => f . write_fmt
+				(
+					format_args! ("Branch {} ({})", branch, boxed_binding)
+				),
+				None => f . write_fmt (format_args! ("Branch {}", branch))
+			}
 		}
 	}
 }
@@ -334,6 +355,36 @@ impl <V> StructuredBindings <V>
 		Ok (())
 	}
 
+	// Parameters that the chosen branch does not mention are still bound, so
+	// that every parameter of the alternation records which branch matched.
+	pub fn add_alternation_bindings <'a, I>
+	(
+		&mut self,
+		idents: I,
+		branch: usize,
+		mut branch_bindings: StructuredBindings <V>
+	)
+	-> Result <(), StructuredBindingMergeError <V>>
+	where
+		I: IntoIterator <Item = &'a Ident>,
+		V: Clone + PartialEq
+	{
+		for ident in idents
+		{
+			self . add_binding
+			(
+				ident . clone (),
+				StructuredBinding::Alternation
+				(
+					branch,
+					branch_bindings . map . remove (ident) . map (Box::new)
+				)
+			)?;
+		}
+
+		Ok (())
+	}
+
 	pub fn merge (&mut self, other: Self)
 	-> Result <(), StructuredBindingMergeError <V>>
 	where V: Clone + PartialEq
@@ -792,6 +843,40 @@ impl <'a, V> StructuredBindingView <'a, V>
 
 		Ok (Some (Self {map}))
 	}
+
+	pub fn get_alternation_view (&self)
+	-> Result <Option <(usize, Self)>, StructuredBindingTypeMismatch>
+	{
+		let mut map = HashMap::new ();
+		let mut chosen_branch = None;
+
+		for (ident, binding) in &self . map
+		{
+			match binding
+			{
+				StructuredBinding::Alternation (branch, option) =>
+				{
+					chosen_branch = Some (*branch);
+
+					if let Some (boxed_binding) = option
+					{
+						map . insert (ident . clone (), &**boxed_binding);
+					}
+				},
+				_ => return Err
+				(
+					StructuredBindingTypeMismatch::new
+					(
+						ident . clone (),
+						binding . ty (),
+						StructuredBindingType::Alternation
+					)
+				)
+			};
+		}
+
+		Ok (chosen_branch . map (|branch| (branch, Self {map})))
+	}
 }
 
 #[derive (Clone, Debug)]
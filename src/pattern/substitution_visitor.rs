@@ -18,6 +18,7 @@ use super::{
 	OptionalVisitor,
 	ZeroOrMoreVisitor,
 	OneOrMoreVisitor,
+	AlternationVisitor,
 	RepetitionLenMismatch,
 	TokenizeBinding
 };
@@ -48,6 +49,7 @@ where T: TokenizeBinding <V>
 	type OptionalVisitor = SubstitutionOptionalVisitor <'a, V>;
 	type ZeroOrMoreVisitor = SubstitutionXOrMoreVisitor <'a, V>;
 	type OneOrMoreVisitor = SubstitutionXOrMoreVisitor <'a, V>;
+	type AlternationVisitor = SubstitutionAlternationVisitor <'a, V>;
 	type GroupVisitor = SubstitutionVisitor <'a, V>;
 
 	fn visit_parameter (&mut self, parameter: &Parameter <T>)
@@ -192,6 +194,36 @@ where T: TokenizeBinding <V>
 		Ok (())
 	}
 
+	fn pre_visit_alternation <'b, I> (&mut self, alternation_parameters: I)
+	-> Result <Self::AlternationVisitor, Self::Error>
+	where I: IntoIterator <Item = &'b Ident>
+	{
+		Ok
+		(
+			Self::AlternationVisitor::new
+			(
+				self
+					. bindings
+					. project (alternation_parameters)?
+					. get_alternation_view ()?
+			)
+		)
+	}
+
+	fn post_visit_alternation <'b, I>
+	(
+		&mut self,
+		_alternation_parameters: I,
+		alternation_visitor: Self::AlternationVisitor
+	)
+	-> Result <(), Self::Error>
+	where I: IntoIterator <Item = &'b Ident>
+	{
+		self . tokens . extend (alternation_visitor . tokens);
+
+		Ok (())
+	}
+
 	fn pre_visit_group
 	(
 		&mut self,
@@ -287,6 +319,59 @@ where T: TokenizeBinding <V>
 	}
 }
 
+pub (in crate::pattern) struct SubstitutionAlternationVisitor <'a, V>
+{
+	chosen_branch: Option <(usize, StructuredBindingView <'a, V>)>,
+	tokens: TokenStream
+}
+
+impl <'a, V> SubstitutionAlternationVisitor <'a, V>
+{
+	pub fn new (chosen_branch: Option <(usize, StructuredBindingView <'a, V>)>) -> Self
+	{
+		Self {chosen_branch, tokens: TokenStream::new ()}
+	}
+}
+
+impl <'a, V, T> AlternationVisitor <T> for SubstitutionAlternationVisitor <'a, V>
+where T: TokenizeBinding <V>
+{
+	type Error = SubstitutionError <T::Error>;
+	type BranchVisitor = SubstitutionVisitor <'a, V>;
+
+	fn pre_visit_branch (&mut self, branch: usize)
+	-> Result <Option <Self::BranchVisitor>, Self::Error>
+	{
+		match self . chosen_branch . take ()
+		{
+			Some ((chosen_branch, bindings)) if chosen_branch == branch =>
+				Ok (Some (SubstitutionVisitor::new (bindings))),
+			chosen_branch =>
+			{
+				self . chosen_branch = chosen_branch;
+
+				Ok (None)
+			}
+		}
+	}
+
+	fn post_visit_branch
+	(
+		&mut self,
+		_branch: usize,
+		branch_visitor: Self::BranchVisitor,
+		visit_result: Result <(), VisitationError <Self::Error>>
+	)
+	-> Result <bool, VisitationError <Self::Error>>
+	{
+		visit_result?;
+
+		self . tokens = branch_visitor . tokens;
+
+		Ok (true)
+	}
+}
+
 pub (in crate::pattern) struct SubstitutionXOrMoreVisitor <'a, V>
 {
 	bindings: StructuredBindingView <'a, V>,
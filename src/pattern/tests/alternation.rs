@@ -0,0 +1,138 @@
+use proc_macro2::TokenStream;
+use syn::parse2;
+use quote::quote;
+
+use super::{pattern, expect_bindings};
+use crate::pattern::{
+	MatchStrategy,
+	TypeAnnotation,
+	Fragment,
+	FragmentValue,
+	PatternBuffer,
+	PatternItem
+};
+
+fn first_item (tokens: TokenStream) -> PatternItem <TypeAnnotation <Fragment>>
+{
+	let pattern_buffer: PatternBuffer <TypeAnnotation <Fragment>> = parse2 (tokens) . unwrap ();
+
+	pattern_buffer . pattern_items () [0] . clone ()
+}
+
+#[test]
+fn first_matching_branch_is_bound ()
+{
+	let pattern = pattern (quote! ($(let $name: ident | fn $func: ident) ;));
+
+	let bindings = pattern . match_tokens::<FragmentValue> (quote! (let x;)) . unwrap ();
+	expect_bindings (&bindings, &[("name", "Branch 0 (x)"), ("func", "Branch 0")]);
+
+	let bindings = pattern . match_tokens::<FragmentValue> (quote! (fn f;)) . unwrap ();
+	expect_bindings (&bindings, &[("name", "Branch 1"), ("func", "Branch 1 (f)")]);
+}
+
+#[test]
+fn shared_parameters_are_bound_in_every_branch ()
+{
+	let pattern = pattern (quote! ($(+ $x: literal | - $x: literal)));
+
+	let bindings = pattern . match_tokens::<FragmentValue> (quote! (- 1)) . unwrap ();
+	expect_bindings (&bindings, &[("x", "Branch 1 (1)")]);
+}
+
+#[test]
+fn no_matching_branch_is_an_error ()
+{
+	let pattern = pattern (quote! ($(let $name: ident | fn $func: ident)));
+
+	assert! (pattern . match_tokens::<FragmentValue> (quote! (struct S)) . is_err ());
+}
+
+#[test]
+fn alternation_inside_a_repetition ()
+{
+	let pattern = pattern (quote! ($($($a: ident | $b: literal)),*));
+
+	let bindings = pattern . match_tokens::<FragmentValue> (quote! (x, 1, y)) . unwrap ();
+
+	expect_bindings
+	(
+		&bindings,
+		&[
+			("a", "[Branch 0 (x), Branch 1, Branch 0 (y)]"),
+			("b", "[Branch 0, Branch 1 (1), Branch 0]")
+		]
+	);
+}
+
+#[test]
+fn groups_followed_by_an_operator_are_repetitions ()
+{
+	assert! (matches! (first_item (quote! ($(a | b)?)), PatternItem::Optional (_)));
+	assert! (matches! (first_item (quote! ($(| $x: ident |)*)), PatternItem::ZeroOrMore (_)));
+	assert! (matches! (first_item (quote! ($(| $x: ident |)+)), PatternItem::OneOrMore (_)));
+	assert! (matches! (first_item (quote! ($(| $x: ident |),*)), PatternItem::ZeroOrMore (_)));
+	assert! (matches! (first_item (quote! ($(| $x: ident |);+)), PatternItem::OneOrMore (_)));
+}
+
+#[test]
+fn repetition_containing_a_bar ()
+{
+	let pattern = pattern (quote! ($(| $x: ident |)*));
+
+	let bindings = pattern . match_tokens::<FragmentValue> (quote! (|a| |b|)) . unwrap ();
+	expect_bindings (&bindings, &[("x", "[a, b]")]);
+
+	assert_eq!
+	(
+		pattern . to_macro_rules_matcher () . unwrap () . to_string (),
+		quote! ($(| $x: ident |)*) . to_string ()
+	);
+}
+
+#[test]
+fn separated_repetition_containing_a_bar ()
+{
+	let pattern = pattern (quote! ($(| $x: ident |),+));
+
+	let bindings = pattern . match_tokens::<FragmentValue> (quote! (|a|, |b|)) . unwrap ();
+	expect_bindings (&bindings, &[("x", "[a, b]")]);
+}
+
+#[test]
+fn groups_without_a_top_level_bar_are_repetitions ()
+{
+	let tokens = quote! ($([$a: ident | $b: ident])*);
+
+	assert! (matches! (first_item (tokens), PatternItem::ZeroOrMore (_)));
+}
+
+#[test]
+fn backtracking_tries_later_branches ()
+{
+	let pattern = pattern (quote! ($($a: tt $b: tt | $c: tt) ;));
+	let strategy = MatchStrategy::Backtracking {step_budget: 1000};
+
+	let bindings = pattern
+		. match_tokens_with::<FragmentValue> (quote! (x ;), strategy)
+		. unwrap ();
+
+	expect_bindings (&bindings, &[("a", "Branch 1"), ("b", "Branch 1"), ("c", "Branch 1 (x)")]);
+}
+
+#[test]
+fn substitution_re_emits_the_chosen_branch ()
+{
+	let pattern = pattern (quote! ($(let $name: ident | fn $func: ident ()) ;));
+
+	for input in [quote! (let x;), quote! (fn f ();)]
+	{
+		let bindings = pattern . match_tokens::<FragmentValue> (input . clone ()) . unwrap ();
+
+		assert_eq!
+		(
+			pattern . substitute (&bindings) . unwrap () . to_string (),
+			input . to_string ()
+		);
+	}
+}
@@ -0,0 +1,93 @@
+use quote::quote;
+
+use super::{pattern, expect_bindings};
+use crate::pattern::{MatchStrategy, FragmentValue};
+
+const BACKTRACKING: MatchStrategy = MatchStrategy::Backtracking {step_budget: 1000};
+
+#[test]
+fn repetition_gives_back_its_last_iteration ()
+{
+	let pattern = pattern (quote! ($($x: ident),* , $last: ident));
+
+	let bindings = pattern
+		. match_tokens_with::<FragmentValue> (quote! (a, b, c), BACKTRACKING)
+		. unwrap ();
+
+	expect_bindings (&bindings, &[("x", "[a, b]"), ("last", "c")]);
+}
+
+#[test]
+fn repetition_stops_before_a_separator ()
+{
+	let pattern = pattern (quote! ($($a: tt)* ; $b: tt));
+
+	let bindings = pattern
+		. match_tokens_with::<FragmentValue> (quote! (x y ; z), BACKTRACKING)
+		. unwrap ();
+
+	expect_bindings (&bindings, &[("a", "[x, y]"), ("b", "z")]);
+}
+
+#[test]
+fn greedy_matching_does_not_backtrack ()
+{
+	let pattern = pattern (quote! ($($x: ident),* , $last: ident));
+
+	assert! (pattern . match_tokens::<FragmentValue> (quote! (a, b, c)) . is_err ());
+}
+
+#[test]
+fn optional_is_dropped_when_the_rest_fails ()
+{
+	let pattern = pattern (quote! ($($first: ident)? $second: ident));
+
+	let bindings = pattern
+		. match_tokens_with::<FragmentValue> (quote! (a), BACKTRACKING)
+		. unwrap ();
+
+	expect_bindings (&bindings, &[("first", "None"), ("second", "a")]);
+}
+
+#[test]
+fn whole_input_must_match ()
+{
+	let pattern = pattern (quote! ($($x: ident),* , $last: ident));
+
+	assert!
+	(
+		pattern
+			. match_tokens_with::<FragmentValue> (quote! (a, b, c d), BACKTRACKING)
+			. is_err ()
+	);
+}
+
+#[test]
+fn exhausted_budget_is_reported ()
+{
+	let pattern = pattern (quote! ($($($a: tt)*)* ; $b: tt));
+	let strategy = MatchStrategy::Backtracking {step_budget: 50};
+
+	let error = pattern
+		. match_tokens_with::<FragmentValue> (quote! (a b c d e f g h), strategy)
+		. err ()
+		. unwrap ()
+		. to_string ();
+
+	assert_eq! (error, "pattern matching gave up after 50 steps");
+}
+
+#[test]
+fn budget_exhausted_at_end_of_input ()
+{
+	let pattern = pattern (quote! ($($x: ident),* , $last: ident));
+	let strategy = MatchStrategy::Backtracking {step_budget: 3};
+
+	let error = pattern
+		. match_tokens_with::<FragmentValue> (quote! (a, b), strategy)
+		. err ()
+		. unwrap ()
+		. to_string ();
+
+	assert_eq! (error, "pattern matching gave up after 3 steps");
+}
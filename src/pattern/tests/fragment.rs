@@ -0,0 +1,67 @@
+use quote::quote;
+
+use super::{pattern, expect_bindings};
+use crate::pattern::FragmentValue;
+
+#[test]
+fn stmt_stops_before_its_semicolon ()
+{
+	let pattern = pattern (quote! ($($s: stmt);*));
+
+	let input = quote! (let x: u8 = 1; let Some (y) = z else { return }; x + 1; f ());
+	let bindings = pattern . match_tokens::<FragmentValue> (input . clone ()) . unwrap ();
+
+	expect_bindings
+	(
+		&bindings,
+		&[("s", "[let x : u8 = 1, let Some (y) = z else { return }, x + 1, f ()]")]
+	);
+
+	assert_eq! (pattern . substitute (&bindings) . unwrap () . to_string (), input . to_string ());
+}
+
+#[test]
+fn stmt_without_a_semicolon_at_the_end ()
+{
+	let pattern = pattern (quote! ($s: stmt));
+
+	for input in [quote! (let x = 1), quote! (x += 1), quote! (if a { b } else { c })]
+	{
+		let bindings = pattern . match_tokens::<FragmentValue> (input . clone ()) . unwrap ();
+
+		assert_eq! (pattern . substitute (&bindings) . unwrap () . to_string (), input . to_string ());
+	}
+}
+
+#[test]
+fn item_stmt_keeps_its_semicolon ()
+{
+	let pattern = pattern (quote! ($s: stmt $($rest: tt)*));
+
+	let bindings = pattern
+		. match_tokens::<FragmentValue> (quote! (struct Unit; x))
+		. unwrap ();
+
+	expect_bindings (&bindings, &[("s", "struct Unit ;"), ("rest", "[x]")]);
+}
+
+#[test]
+fn literal_accepts_a_leading_minus ()
+{
+	let pattern = pattern (quote! ($($l: literal),*));
+
+	let input = quote! (1, -1, -2.5, "a", 'b');
+	let bindings = pattern . match_tokens::<FragmentValue> (input . clone ()) . unwrap ();
+
+	expect_bindings (&bindings, &[("l", "[1, - 1, - 2.5, \"a\", 'b']")]);
+	assert_eq! (pattern . substitute (&bindings) . unwrap () . to_string (), input . to_string ());
+}
+
+#[test]
+fn minus_alone_is_not_a_literal ()
+{
+	let pattern = pattern (quote! ($l: literal));
+
+	assert! (pattern . match_tokens::<FragmentValue> (quote! (-)) . is_err ());
+	assert! (pattern . match_tokens::<FragmentValue> (quote! (-x)) . is_err ());
+}
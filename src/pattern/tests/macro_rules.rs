@@ -0,0 +1,119 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use super::pattern;
+use crate::pattern::NoMacroRulesEquivalent;
+
+fn matcher (tokens: TokenStream) -> Result <String, NoMacroRulesEquivalent>
+{
+	pattern (tokens) . to_macro_rules_matcher () . map (|matcher| matcher . to_string ())
+}
+
+fn expect_illegal_follower (tokens: TokenStream, expected: &str)
+{
+	match matcher (tokens)
+	{
+		Err (error @ NoMacroRulesEquivalent::IllegalFollower (..)) =>
+			assert_eq! (error . to_string (), expected),
+		result => panic! ("Expected an illegal follower, got {:?}", result)
+	}
+}
+
+#[test]
+fn expr_followed_by_an_operator_is_rejected ()
+{
+	expect_illegal_follower
+	(
+		quote! ($x: expr + $y: expr),
+		"`$x: expr` is followed by `+`, which macro_rules does not allow after `expr` fragments"
+	);
+}
+
+#[test]
+fn expr_followed_by_its_follow_set_is_accepted ()
+{
+	assert_eq!
+	(
+		matcher (quote! ($x: expr, $y: expr => $z: expr;)) . unwrap (),
+		quote! ($x: expr, $y: expr => $z: expr;) . to_string ()
+	);
+}
+
+#[test]
+fn joint_punctuation_is_one_follower ()
+{
+	assert! (matcher (quote! ($t: ty >> $u: ty)) . is_ok ());
+
+	expect_illegal_follower
+	(
+		quote! ($x: expr => $y: expr ==),
+		"`$y: expr` is followed by `==`, which macro_rules does not allow after `expr` fragments"
+	);
+}
+
+#[test]
+fn repetitions_check_their_separator_and_their_own_start ()
+{
+	assert! (matcher (quote! ($($x: expr),*)) . is_ok ());
+	assert! (matcher (quote! ($($x: expr;)+)) . is_ok ());
+
+	expect_illegal_follower
+	(
+		quote! ($($x: expr)*),
+		"`$x: expr` is followed by `$x: expr`, which macro_rules does not allow after `expr` fragments"
+	);
+}
+
+#[test]
+fn followers_are_found_past_optional_items ()
+{
+	assert! (matcher (quote! ($x: expr $(; $y: ident)? => $z: tt)) . is_ok ());
+
+	expect_illegal_follower
+	(
+		quote! ($x: expr $(, $y: ident)? + $z: tt),
+		"`$x: expr` is followed by `+`, which macro_rules does not allow after `expr` fragments"
+	);
+	expect_illegal_follower
+	(
+		quote! ($($x: expr),* $y: ident),
+		"`$x: expr` is followed by `$y: ident`, which macro_rules does not allow after `expr` fragments"
+	);
+}
+
+#[test]
+fn group_ends_are_always_allowed ()
+{
+	assert! (matcher (quote! (($x: expr) + [$y: stmt] $z: ident)) . is_ok ());
+}
+
+#[test]
+fn ty_pat_and_vis_have_their_own_follow_sets ()
+{
+	assert! (matcher (quote! ($t: ty where $u: path {})) . is_ok ());
+	assert! (matcher (quote! ($p: pat if $c: expr)) . is_ok ());
+	assert! (matcher (quote! ($v: vis fn $f: ident)) . is_ok ());
+	assert! (matcher (quote! ($v: vis $t: ty)) . is_ok ());
+
+	expect_illegal_follower
+	(
+		quote! ($t: ty + $u: ty),
+		"`$t: ty` is followed by `+`, which macro_rules does not allow after `ty` fragments"
+	);
+	expect_illegal_follower
+	(
+		quote! ($p: pat => $q: pat $r: tt),
+		"`$q: pat` is followed by `$r: tt`, which macro_rules does not allow after `pat` fragments"
+	);
+	expect_illegal_follower
+	(
+		quote! ($v: vis $e: expr),
+		"`$v: vis` is followed by `$e: expr`, which macro_rules does not allow after `vis` fragments"
+	);
+}
+
+#[test]
+fn other_fragments_accept_any_follower ()
+{
+	assert! (matcher (quote! ($i: ident + $l: literal $b: block $t: tt $x: expr)) . is_ok ());
+}
@@ -0,0 +1,36 @@
+use std::collections::BTreeMap;
+
+use proc_macro2::TokenStream;
+use syn::parse2;
+
+use super::{Pattern, TypeAnnotation, Fragment, FragmentValue, StructuredBindings};
+
+mod backtracking;
+mod alternation;
+mod fragment;
+mod macro_rules;
+
+type FragmentPattern = Pattern <TypeAnnotation <Fragment>>;
+
+fn pattern (tokens: TokenStream) -> FragmentPattern
+{
+	parse2 (tokens) . unwrap ()
+}
+
+fn describe (bindings: &StructuredBindings <FragmentValue>) -> BTreeMap <String, String>
+{
+	bindings
+		. into_iter ()
+		. map (|(ident, binding)| (ident . to_string (), binding . to_string ()))
+		. collect ()
+}
+
+fn expect_bindings (bindings: &StructuredBindings <FragmentValue>, expected: &[(&str, &str)])
+{
+	let expected: BTreeMap <String, String> = expected
+		. iter ()
+		. map (|(ident, binding)| (ident . to_string (), binding . to_string ()))
+		. collect ();
+
+	assert_eq! (describe (bindings), expected);
+}
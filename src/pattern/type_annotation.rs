@@ -6,7 +6,7 @@ use syn::Ident;
 use syn::parse::{Parse, ParseStream};
 use quote::ToTokens;
 
-use super::{ParseBinding, TokenizeBinding};
+use super::{ParseBinding, TokenizeBinding, MacroRulesBinding, Fragment};
 
 #[derive (Copy, Clone, Debug, PartialEq, Eq)]
 pub struct TypeAnnotation <T>
@@ -67,6 +67,15 @@ where T: TokenizeBinding <V>
 	}
 }
 
+impl <T> MacroRulesBinding for TypeAnnotation <T>
+where T: MacroRulesBinding
+{
+	fn fragment (&self) -> Option <Fragment>
+	{
+		self . ty . fragment ()
+	}
+}
+
 #[derive (Clone, Debug)]
 pub struct ParameterBindingTypeMismatch <V, T>
 {
@@ -0,0 +1,82 @@
+#[macrospace::item (recursive)]
+pub mod schema
+{
+	pub struct Config;
+
+	impl Config
+	{
+		pub fn new () -> Self
+		{
+			Self
+		}
+	}
+
+	pub mod a
+	{
+		pub struct Shared;
+	}
+
+	pub mod b
+	{
+		pub struct Shared;
+	}
+}
+
+pub fn helper () -> u8
+{
+	1
+}
+
+pub mod bodies
+{
+	#[macrospace::item]
+	pub fn full () -> u8
+	{
+		super::helper ()
+	}
+
+	#[macrospace::item (signature)]
+	pub fn signature () -> u8
+	{
+		super::helper ()
+	}
+}
+
+#[test]
+fn relative_paths_in_bodies_do_not_need_a_module ()
+{
+	const FULL: &str = macrospace::item_source! (crate::bodies::full: fn);
+	const SIGNATURE: &str = macrospace::item_source! (crate::bodies::signature: fn);
+
+	assert_eq! (FULL, "pub fn full() -> u8 { super :: helper() }");
+	assert_eq! (SIGNATURE, "pub fn signature() -> u8;");
+}
+
+#[test]
+fn recursive_export_skips_impl_blocks ()
+{
+	const SOURCE: &str = macrospace::item_source! (crate::schema::Config: struct);
+
+	assert_eq! (SOURCE, "pub struct Config;");
+}
+
+#[test]
+fn identical_items_in_sibling_modules_are_exported_separately ()
+{
+	const A: &str = macrospace::item_source! (crate::schema::a::Shared: struct);
+	const B: &str = macrospace::item_source! (crate::schema::b::Shared: struct);
+
+	assert_eq! (A, B);
+}
+
+#[test]
+fn checked_requests_continue_after_the_check ()
+{
+	const SOURCE: &str = macrospace::item_source!
+	(
+		crate::schema::a::Shared: struct | enum,
+		crate::bodies::full: fn
+	);
+
+	assert_eq! (SOURCE, "pub struct Shared; pub fn full() -> u8 { super :: helper() }");
+}